@@ -0,0 +1,56 @@
+// Compares the global generator modes under contention. Run it once as is for
+// the mutex-backed shared mode and once with `--features atomic` for the
+// compare-and-swap one:
+//
+//     cargo bench --bench global
+//     cargo bench --bench global --features atomic
+extern crate simplerand;
+
+use simplerand::{rand, set_global_mode, GlobalMode};
+use std::thread;
+use std::time::Instant;
+
+const DRAWS: usize = 1_000_000;
+
+fn run(mode: GlobalMode, threads: usize) {
+    set_global_mode(mode);
+
+    let now = Instant::now();
+    let handles: Vec<_> = (0..threads)
+        .map(|_| {
+            thread::spawn(move || {
+                let mut sum = 0u64;
+                for _ in 0..DRAWS / threads {
+                    sum = sum.wrapping_add(rand::<u64>());
+                }
+                sum
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    let elapsed = now.elapsed();
+
+    println!(
+        "{:?} with {} threads: {:.2?} total, {:.1} ns per draw",
+        mode,
+        threads,
+        elapsed,
+        elapsed.as_nanos() as f64 / DRAWS as f64
+    );
+}
+
+fn main() {
+    let shared = if cfg!(feature = "atomic") {
+        "atomic"
+    } else {
+        "mutex"
+    };
+    println!("shared generator backed by: {}", shared);
+
+    for threads in [1, 2, 4, 8].iter() {
+        run(GlobalMode::ThreadLocal, *threads);
+        run(GlobalMode::Shared, *threads);
+    }
+}
@@ -0,0 +1,83 @@
+// Compares the multiply-shift bounded generation of `randn` with the
+// rejection-and-modulo one of 1.x, on bounds of every width:
+//
+//     cargo bench --bench randn
+extern crate simplerand;
+
+use simplerand::Rng;
+use std::time::Instant;
+
+const DRAWS: usize = 1_000_000;
+
+// The 1.x generator and its `randn`, ported as they were: a 63-bit LCG step per
+// draw, rejection above the largest multiple of n below 2^63, then a modulo
+const A: u128 = 1103515245;
+const C: u128 = 12345;
+const M: u128 = 1 << 63;
+
+struct Legacy {
+    seed: u128,
+}
+
+impl Legacy {
+    fn rand(&mut self) -> u128 {
+        self.seed = (A * self.seed + C) % M;
+        self.seed
+    }
+
+    fn randn(&mut self, n: u128) -> u128 {
+        if n & (n - 1) == 0 {
+            return self.rand() & (n - 1);
+        }
+
+        // Above 2^63 every draw is accepted and the result never reaches n
+        let max = if n > M { M - 1 } else { M - 1 - (M % n) };
+        let mut v = self.rand();
+        while v > max {
+            v = self.rand();
+        }
+        v % n
+    }
+}
+
+fn run_legacy(n: u128) {
+    let mut rng = Legacy { seed: 1 };
+    time("legacy", n, || rng.randn(n));
+}
+
+fn run_lemire(n: u128) {
+    let mut rng: Rng = Rng::with_seed(1);
+    time("lemire", n, || rng.randn(n));
+}
+
+fn time(name: &str, n: u128, mut f: impl FnMut() -> u128) {
+    let now = Instant::now();
+    let mut sum = 0u128;
+    for _ in 0..DRAWS {
+        sum = sum.wrapping_add(f());
+    }
+    let elapsed = now.elapsed();
+
+    println!(
+        "{} n={}: {:.2?} total, {:.1} ns per draw (checksum {})",
+        name,
+        n,
+        elapsed,
+        elapsed.as_nanos() as f64 / DRAWS as f64,
+        sum % 10
+    );
+}
+
+fn main() {
+    let bounds = [
+        10,
+        (1 << 31) + 1,
+        (1 << 63) + 1,
+        u64::MAX as u128,
+        (1 << 100) + 12345,
+    ];
+    for n in bounds.iter() {
+        run_legacy(*n);
+        run_lemire(*n);
+    }
+}
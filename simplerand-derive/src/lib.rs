@@ -0,0 +1,237 @@
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::visit::{self, Visit};
+use syn::{
+    parse_macro_input, parse_quote, Attribute, Data, DeriveInput, Error, Expr, ExprLit, Field,
+    Fields, Generics, Ident, Lit, LitStr, Result, TypePath, WherePredicate,
+};
+
+/// Derives `simplerand::Randomable`.
+///
+/// Struct fields are drawn one by one with their own `Randomable` impl, or
+/// from a range with `#[rand(range = "1..100")]`. Enum variants are picked
+/// uniformly, or in proportion to `#[weight = N]`, a missing weight counting as 1.
+#[proc_macro_derive(Randomable, attributes(rand, weight))]
+pub fn derive_randomable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand(mut input: DeriveInput) -> Result<TokenStream2> {
+    let (body, fields) = match &input.data {
+        Data::Struct(data) => (
+            construct(quote!(Self), &data.fields)?,
+            data.fields.iter().collect(),
+        ),
+        Data::Enum(data) => (choose_variant(&input, data)?, drawn_fields(data)?),
+        Data::Union(_) => {
+            return Err(Error::new(
+                input.ident.span(),
+                "Randomable cannot be derived for unions",
+            ))
+        }
+    };
+
+    let bounds = field_bounds(&input.generics, &fields)?;
+    input.generics.make_where_clause().predicates.extend(bounds);
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::simplerand::Randomable for #name #ty_generics #where_clause {
+            fn rand<__E: ::simplerand::Engine>(rng: &mut ::simplerand::Rng<__E>) -> Self {
+                #body
+            }
+        }
+    })
+}
+
+// The fields of the variants that can be picked, zero weights are never constructed
+fn drawn_fields(data: &syn::DataEnum) -> Result<Vec<&Field>> {
+    let mut fields = Vec::new();
+    for variant in data.variants.iter() {
+        if variant_weight(&variant.attrs)? != Some(0) {
+            fields.extend(variant.fields.iter());
+        }
+    }
+    Ok(fields)
+}
+
+// Only the type parameters of fields drawn with their own impl need `Randomable`,
+// a range field needs its type to be `RandomableRange` and `PhantomData` nothing
+fn field_bounds(generics: &Generics, fields: &[&Field]) -> Result<Vec<WherePredicate>> {
+    let params: Vec<&Ident> = generics.type_params().map(|param| &param.ident).collect();
+    let mut drawn = Vec::new();
+    let mut bounds = Vec::new();
+    for field in fields {
+        let mut visitor = ParamVisitor {
+            params: &params,
+            found: Vec::new(),
+        };
+        visitor.visit_type(&field.ty);
+        if visitor.found.is_empty() {
+            continue;
+        }
+
+        let ty = &field.ty;
+        if field_range(&field.attrs)?.is_some() {
+            bounds.push(parse_quote!(#ty: ::simplerand::RandomableRange));
+        } else {
+            drawn.extend(visitor.found);
+        }
+    }
+
+    for param in params {
+        if drawn.contains(param) {
+            bounds.push(parse_quote!(#param: ::simplerand::Randomable));
+        }
+    }
+    Ok(bounds)
+}
+
+// Collects the type parameters a field type mentions outside of `PhantomData`
+struct ParamVisitor<'a> {
+    params: &'a [&'a Ident],
+    found: Vec<Ident>,
+}
+
+impl<'ast> Visit<'ast> for ParamVisitor<'_> {
+    fn visit_type_path(&mut self, path: &'ast TypePath) {
+        if path.qself.is_none() {
+            if let Some(first) = path.path.segments.first() {
+                if let Some(param) = self.params.iter().find(|param| **param == &first.ident) {
+                    self.found.push((*param).clone());
+                }
+            }
+        }
+        match path.path.segments.last() {
+            Some(last) if last.ident == "PhantomData" => {}
+            _ => visit::visit_type_path(self, path),
+        }
+    }
+}
+
+fn construct(path: TokenStream2, fields: &Fields) -> Result<TokenStream2> {
+    match fields {
+        Fields::Named(named) => {
+            let values = named
+                .named
+                .iter()
+                .map(|field| {
+                    let name = &field.ident;
+                    let value = draw_field(field)?;
+                    Ok(quote!(#name: #value))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(quote!(#path { #(#values),* }))
+        }
+        Fields::Unnamed(unnamed) => {
+            let values = unnamed
+                .unnamed
+                .iter()
+                .map(draw_field)
+                .collect::<Result<Vec<_>>>()?;
+            Ok(quote!(#path(#(#values),*)))
+        }
+        Fields::Unit => Ok(path),
+    }
+}
+
+fn draw_field(field: &syn::Field) -> Result<TokenStream2> {
+    let ty = &field.ty;
+    match field_range(&field.attrs)? {
+        Some(range) => Ok(quote_spanned! {range.span()=>
+            <#ty as ::simplerand::RandomableRange>::gen_range(rng, #range)
+        }),
+        None => Ok(quote_spanned! {ty.span()=>
+            <#ty as ::simplerand::Randomable>::rand(rng)
+        }),
+    }
+}
+
+// #[rand(range = "1..100")]
+fn field_range(attrs: &[Attribute]) -> Result<Option<Expr>> {
+    let mut range = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("rand")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("range") {
+                let value: LitStr = meta.value()?.parse()?;
+                range = Some(value.parse::<Expr>()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `range = \"..\"`"))
+            }
+        })?;
+    }
+    Ok(range)
+}
+
+// #[weight = 3]
+fn variant_weight(attrs: &[Attribute]) -> Result<Option<u64>> {
+    let attr = match attrs.iter().find(|attr| attr.path().is_ident("weight")) {
+        Some(attr) => attr,
+        None => return Ok(None),
+    };
+    match &attr.meta.require_name_value()?.value {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(weight),
+            ..
+        }) => weight.base10_parse().map(Some),
+        other => Err(Error::new(other.span(), "expected an integer weight")),
+    }
+}
+
+fn choose_variant(input: &DeriveInput, data: &syn::DataEnum) -> Result<TokenStream2> {
+    if data.variants.is_empty() {
+        return Err(Error::new(
+            input.ident.span(),
+            "Randomable cannot be derived for an enum without variants",
+        ));
+    }
+
+    let weights = data
+        .variants
+        .iter()
+        .map(|variant| variant_weight(&variant.attrs))
+        .collect::<Result<Vec<_>>>()?;
+
+    // Variants without a weight count as 1
+    let mut total = 0u64;
+    let mut arms = Vec::new();
+    for (variant, weight) in data.variants.iter().zip(weights) {
+        let weight = weight.unwrap_or(1);
+        if weight == 0 {
+            continue;
+        }
+        let ident = &variant.ident;
+        let value = construct(quote!(Self::#ident), &variant.fields)?;
+
+        let start = total;
+        total = total
+            .checked_add(weight)
+            .ok_or_else(|| Error::new(variant.span(), "the weights overflow u64"))?;
+        let end = total - 1;
+        arms.push(quote!(#start..=#end => #value));
+    }
+
+    if total == 0 {
+        return Err(Error::new(
+            input.ident.span(),
+            "at least one variant needs a weight above 0",
+        ));
+    }
+
+    Ok(quote! {
+        match rng.randn_u64(#total) {
+            #(#arms,)*
+            _ => unreachable!(),
+        }
+    })
+}
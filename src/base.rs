@@ -1,42 +1,86 @@
-use std::time::{SystemTime, UNIX_EPOCH};
-
-const A: i64 = 1103515245;
-const C: i16 = 12345;
-const M: u64 = 1 << 63;
-
-#[derive(Debug, Copy, Clone)]
-pub struct Rng {
+use crate::engine::{Engine, Lcg};
+use crate::error::Error;
+use crate::interval::{Closed01, Interval, Standard, UnitFloat};
+use std::fmt;
+use std::ops::{Bound, RangeBounds};
+
+#[derive(Copy, Clone)]
+pub struct Rng<E = Lcg> {
     seed: u128,
+    engine: E,
 }
 
-impl Default for Rng {
+// The seed reproduces the whole stream, only the engine decides what it shows
+impl<E: fmt::Debug> fmt::Debug for Rng<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Rng").field("engine", &self.engine).finish()
+    }
+}
+
+impl<E: Engine> Default for Rng<E> {
     fn default() -> Self {
-        Self::new()
+        Self::from_entropy()
     }
 }
 
 impl Rng {
     pub fn new() -> Rng {
+        Rng::default()
+    }
+}
+
+impl<E: Engine> Rng<E> {
+    pub fn with_seed(s: u128) -> Rng<E> {
         Rng {
-            seed: SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap()
-                .as_nanos(),
+            seed: s,
+            engine: E::from_seed(s),
         }
     }
 
+    /// Seeds from `getrandom(2)` or `/dev/urandom`, falling back to time, pid and a counter.
+    /// The seed is not kept, `get_seed` returns 0 until `set_seed` is called.
+    pub fn from_entropy() -> Rng<E> {
+        Rng::from_engine(E::from_entropy())
+    }
+
+    /// Wraps an already initialized engine, `get_seed` returns 0 until `set_seed` is called.
+    pub fn from_engine(engine: E) -> Rng<E> {
+        Rng { seed: 0, engine }
+    }
+
     pub fn set_seed(&mut self, s: u128) {
         self.seed = s;
+        self.engine = E::from_seed(s);
     }
 
-    pub fn get_seed(self) -> u128 {
+    pub fn get_seed(&self) -> u128 {
         self.seed
     }
 
+    pub fn engine(&self) -> &E {
+        &self.engine
+    }
+
+    pub(crate) fn engine_mut(&mut self) -> &mut E {
+        &mut self.engine
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        self.engine.next_u32()
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.engine.next_u64()
+    }
+
+    pub fn next_u128(&mut self) -> u128 {
+        let low = self.engine.next_u64() as u128;
+        let high = self.engine.next_u64() as u128;
+        (high << 64) | low
+    }
+
     pub fn rand(&mut self) -> u128 {
-        // https://stackoverflow.com/questions/3062746/special-simple-random-number-generator
-        self.seed = (A as u128 * self.seed + C as u128) % M as u128;
-        self.seed
+        self.next_u128()
     }
 
     pub fn randn(&mut self, n: u128) -> u128 {
@@ -44,295 +88,831 @@ impl Rng {
             panic!("invalid argument, must be bigger than 0");
         }
 
+        if n <= u32::MAX as u128 {
+            return self.randn_u32(n as u32) as u128;
+        }
+        if n <= u64::MAX as u128 {
+            return self.randn_u64(n as u64) as u128;
+        }
+
         if n & (n - 1) == 0 {
             // n is power of two, can mask
             return self.rand() & (n - 1);
         }
 
-        // Ensure we don't subtract more than we have
-        let max: u128 = if n > (1 << 63) {
-            // If n is larger than (1 << 63), avoid overflow
-            (1 << 63) - 1
-        } else {
-            (1 << 63) - 1 - ((1 << 63) % n)
-        };
+        // 2^128 % n, rejecting the values below it leaves a whole number of copies of [0, n)
+        let threshold = n.wrapping_neg() % n;
 
         let mut v = self.rand();
-        while v > max {
+        while v < threshold {
             v = self.rand();
         }
 
         v % n
     }
 
+    // https://arxiv.org/abs/1805.10941, the division only runs when a rejection is possible
+    pub fn randn_u32(&mut self, n: u32) -> u32 {
+        if n == 0 {
+            panic!("invalid argument, must be bigger than 0");
+        }
+
+        let mut m = self.next_u32() as u64 * n as u64;
+        if (m as u32) < n {
+            let threshold = n.wrapping_neg() % n;
+            while (m as u32) < threshold {
+                m = self.next_u32() as u64 * n as u64;
+            }
+        }
+        (m >> 32) as u32
+    }
+
+    pub fn randn_u64(&mut self, n: u64) -> u64 {
+        if n == 0 {
+            panic!("invalid argument, must be bigger than 0");
+        }
+
+        let mut m = self.next_u64() as u128 * n as u128;
+        if (m as u64) < n {
+            let threshold = n.wrapping_neg() % n;
+            while (m as u64) < threshold {
+                m = self.next_u64() as u128 * n as u128;
+            }
+        }
+        (m >> 64) as u64
+    }
+
     pub fn rand_range(&mut self, min: u128, max: u128) -> u128 {
+        if max <= min {
+            panic!("invalid argument, max must be bigger than min");
+        }
         self.randn(max - min) + min
     }
+
+    pub fn try_randn(&mut self, n: u128) -> Result<u128, Error> {
+        if n == 0 {
+            return Err(Error::EmptyRange);
+        }
+        Ok(self.randn(n))
+    }
+
+    pub fn try_rand_range(&mut self, min: u128, max: u128) -> Result<u128, Error> {
+        if max <= min {
+            return Err(Error::EmptyRange);
+        }
+        Ok(self.rand_range(min, max))
+    }
+
+    pub fn rand_range_inclusive(&mut self, min: u128, max: u128) -> u128 {
+        if max < min {
+            panic!("invalid argument, max must not be smaller than min");
+        }
+        match (max - min).checked_add(1) {
+            Some(n) => self.randn(n) + min,
+            // 0..=u128::MAX, every value is in range
+            None => self.rand(),
+        }
+    }
+
+    pub fn randn_signed(&mut self, n: i128) -> i128 {
+        if n <= 0 {
+            panic!("invalid argument, must be bigger than 0");
+        }
+        self.randn(n as u128) as i128
+    }
+
+    pub fn rand_range_signed(&mut self, min: i128, max: i128) -> i128 {
+        if max <= min {
+            panic!("invalid argument, max must be bigger than min");
+        }
+        // Any signed span fits in u128, the offset wraps back into place
+        let span = max.wrapping_sub(min) as u128;
+        min.wrapping_add(self.randn(span) as i128)
+    }
+
+    pub fn rand_range_inclusive_signed(&mut self, min: i128, max: i128) -> i128 {
+        if max < min {
+            panic!("invalid argument, max must not be smaller than min");
+        }
+        match (max.wrapping_sub(min) as u128).checked_add(1) {
+            Some(n) => min.wrapping_add(self.randn(n) as i128),
+            None => self.rand() as i128,
+        }
+    }
+
+    /// Uniform in [0, 1) with 24 random mantissa bits.
+    pub fn next_f32(&mut self) -> f32 {
+        Standard::f32_from_bits(self.next_u32())
+    }
+
+    /// Uniform in [0, 1) with 53 random mantissa bits.
+    pub fn next_f64(&mut self) -> f64 {
+        Standard::f64_from_bits(self.next_u64())
+    }
+
+    pub fn rand_range_f32(&mut self, min: f32, max: f32) -> f32 {
+        if !min.is_finite() || !max.is_finite() {
+            panic!("invalid argument, bounds must be finite");
+        }
+        if max <= min {
+            panic!("invalid argument, max must be bigger than min");
+        }
+        // Halving keeps the span finite even for f32::MIN..f32::MAX
+        let half = max / 2.0 - min / 2.0;
+        loop {
+            let u = self.next_f32();
+            let v = min + u * half + u * half;
+            // Rounding can land exactly on max
+            if v < max {
+                return v;
+            }
+        }
+    }
+
+    pub fn rand_range_inclusive_f32(&mut self, min: f32, max: f32) -> f32 {
+        if !min.is_finite() || !max.is_finite() {
+            panic!("invalid argument, bounds must be finite");
+        }
+        if max < min {
+            panic!("invalid argument, max must not be smaller than min");
+        }
+        let half = max / 2.0 - min / 2.0;
+        loop {
+            let u = Closed01::f32_from_bits(self.next_u32());
+            let v = min + u * half + u * half;
+            if v <= max {
+                return v;
+            }
+        }
+    }
+
+    pub fn rand_range_f64(&mut self, min: f64, max: f64) -> f64 {
+        if !min.is_finite() || !max.is_finite() {
+            panic!("invalid argument, bounds must be finite");
+        }
+        if max <= min {
+            panic!("invalid argument, max must be bigger than min");
+        }
+        let half = max / 2.0 - min / 2.0;
+        loop {
+            let u = self.next_f64();
+            let v = min + u * half + u * half;
+            if v < max {
+                return v;
+            }
+        }
+    }
+
+    pub fn rand_range_inclusive_f64(&mut self, min: f64, max: f64) -> f64 {
+        if !min.is_finite() || !max.is_finite() {
+            panic!("invalid argument, bounds must be finite");
+        }
+        if max < min {
+            panic!("invalid argument, max must not be smaller than min");
+        }
+        let half = max / 2.0 - min / 2.0;
+        loop {
+            let u = Closed01::f64_from_bits(self.next_u64());
+            let v = min + u * half + u * half;
+            if v <= max {
+                return v;
+            }
+        }
+    }
+
+    /// `true` with probability `p`.
+    pub fn gen_bool(&mut self, p: f64) -> bool {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("invalid argument, p must be between 0 and 1");
+        }
+        if p == 1.0 {
+            return true;
+        }
+        // p scaled to 2^64, exact to the 53 bits p carries
+        let threshold = (p * (1u128 << 64) as f64) as u64;
+        self.next_u64() < threshold
+    }
+
+    /// `gen_bool` that returns an error instead of panicking on `p` outside 0..=1.
+    pub fn try_gen_bool(&mut self, p: f64) -> Result<bool, Error> {
+        if p.is_nan() {
+            return Err(Error::NonFiniteFloat);
+        }
+        if !(0.0..=1.0).contains(&p) {
+            return Err(Error::InvalidParameter);
+        }
+        Ok(self.gen_bool(p))
+    }
+
+    pub fn rand_unit<T: UnitFloat, I: Interval>(&mut self, _interval: I) -> T {
+        T::rand_unit::<I, _>(self)
+    }
+
+    pub fn gen_range<T: RandomableRange, R: RangeBounds<T>>(&mut self, range: R) -> T {
+        T::gen_range(self, range)
+    }
+
+    pub fn try_gen_range<T: RandomableRange, R: RangeBounds<T>>(
+        &mut self,
+        range: R,
+    ) -> Result<T, Error> {
+        T::try_gen_range(self, range)
+    }
 }
 
-pub trait Randomable {
-    fn rand(rng: &mut Rng) -> Self;
-    fn randn(rng: &mut Rng, n: Self) -> Self;
-    fn rand_range(rng: &mut Rng, min: Self, max: Self) -> Self;
+/// Types that can be drawn from any `Rng`, `Random` or the global generator.
+pub trait Randomable: Sized {
+    fn rand<E: Engine>(rng: &mut Rng<E>) -> Self;
+}
 
-    fn into_randomable(self) -> Self;
+/// Types that can also be drawn from a range of values.
+pub trait RandomableRange: Randomable + Copy + PartialOrd {
+    /// The bound used for an unbounded side of a `gen_range` range.
+    const MIN: Self;
+    const MAX: Self;
+    const ZERO: Self;
+
+    /// The smallest value above `self`, `None` at the top of the domain. It turns an
+    /// excluded start bound into an included one.
+    fn successor(self) -> Option<Self>;
+
+    fn randn<E: Engine>(rng: &mut Rng<E>, n: Self) -> Self;
+    fn rand_range<E: Engine>(rng: &mut Rng<E>, min: Self, max: Self) -> Self;
+    fn rand_range_inclusive<E: Engine>(rng: &mut Rng<E>, min: Self, max: Self) -> Self;
+
+    fn gen_range<E: Engine, R: RangeBounds<Self>>(rng: &mut Rng<E>, range: R) -> Self {
+        let min = match range.start_bound() {
+            Bound::Included(min) => *min,
+            Bound::Excluded(start) => match start.successor() {
+                Some(min) => min,
+                None => panic!("invalid argument, nothing is above the excluded start bound"),
+            },
+            Bound::Unbounded => Self::MIN,
+        };
+        match range.end_bound() {
+            Bound::Included(max) => Self::rand_range_inclusive(rng, min, *max),
+            Bound::Excluded(max) => Self::rand_range(rng, min, *max),
+            Bound::Unbounded => Self::rand_range_inclusive(rng, min, Self::MAX),
+        }
+    }
+
+    fn check_range(min: Self, max: Self) -> Result<(), Error> {
+        if min < max {
+            Ok(())
+        } else {
+            Err(Error::EmptyRange)
+        }
+    }
+
+    // `check_range` for `min..=max`, which also holds a single value
+    fn check_inclusive_range(min: Self, max: Self) -> Result<(), Error> {
+        match Self::check_range(min, max) {
+            Err(Error::EmptyRange) if min == max => Ok(()),
+            result => result,
+        }
+    }
+
+    fn try_gen_range<E: Engine, R: RangeBounds<Self>>(
+        rng: &mut Rng<E>,
+        range: R,
+    ) -> Result<Self, Error> {
+        let min = match range.start_bound() {
+            Bound::Included(min) => *min,
+            Bound::Excluded(start) => start.successor().ok_or(Error::EmptyRange)?,
+            Bound::Unbounded => Self::MIN,
+        };
+        match range.end_bound() {
+            Bound::Included(max) => {
+                Self::check_inclusive_range(min, *max)?;
+                Ok(Self::rand_range_inclusive(rng, min, *max))
+            }
+            Bound::Excluded(max) => {
+                Self::check_range(min, *max)?;
+                Ok(Self::rand_range(rng, min, *max))
+            }
+            Bound::Unbounded => {
+                Self::check_inclusive_range(min, Self::MAX)?;
+                Ok(Self::rand_range_inclusive(rng, min, Self::MAX))
+            }
+        }
+    }
+
+    fn try_randn<E: Engine>(rng: &mut Rng<E>, n: Self) -> Result<Self, Error> {
+        if n < Self::ZERO {
+            return Err(Error::InvalidBound);
+        }
+        Self::check_range(Self::ZERO, n)?;
+        Ok(Self::randn(rng, n))
+    }
+
+    fn try_rand_range<E: Engine>(rng: &mut Rng<E>, min: Self, max: Self) -> Result<Self, Error> {
+        Self::check_range(min, max)?;
+        Ok(Self::rand_range(rng, min, max))
+    }
 }
 
 impl Randomable for u8 {
-    fn rand(rng: &mut Rng) -> u8 {
-        let range: u128 = (1 << 8) - 1;
-        rng.rand_range(0, range) as u8
+    fn rand<E: Engine>(rng: &mut Rng<E>) -> u8 {
+        rng.next_u32() as u8
     }
+}
+
+impl RandomableRange for u8 {
+    const MIN: u8 = u8::MIN;
+    const MAX: u8 = u8::MAX;
+    const ZERO: u8 = 0;
 
-    fn randn(rng: &mut Rng, n: u8) -> u8 {
-        rng.randn(n as u128) as u8
+    fn successor(self) -> Option<u8> {
+        self.checked_add(1)
     }
 
-    fn rand_range(rng: &mut Rng, min: u8, max: u8) -> u8 {
+    fn randn<E: Engine>(rng: &mut Rng<E>, n: u8) -> u8 {
+        rng.randn_u32(n as u32) as u8
+    }
+
+    fn rand_range<E: Engine>(rng: &mut Rng<E>, min: u8, max: u8) -> u8 {
         rng.rand_range(min as u128, max as u128) as u8
     }
 
-    fn into_randomable(self) -> u8 {
-        self // No transformation needed
+    fn rand_range_inclusive<E: Engine>(rng: &mut Rng<E>, min: u8, max: u8) -> u8 {
+        rng.rand_range_inclusive(min as u128, max as u128) as u8
     }
 }
 
 impl Randomable for u16 {
-    fn rand(rng: &mut Rng) -> u16 {
-        let range: u128 = (1 << 16) - 1;
-        rng.rand_range(0, range) as u16
+    fn rand<E: Engine>(rng: &mut Rng<E>) -> u16 {
+        rng.next_u32() as u16
+    }
+}
+
+impl RandomableRange for u16 {
+    const MIN: u16 = u16::MIN;
+    const MAX: u16 = u16::MAX;
+    const ZERO: u16 = 0;
+
+    fn successor(self) -> Option<u16> {
+        self.checked_add(1)
     }
 
-    fn randn(rng: &mut Rng, n: u16) -> u16 {
-        rng.randn(n as u128) as u16
+    fn randn<E: Engine>(rng: &mut Rng<E>, n: u16) -> u16 {
+        rng.randn_u32(n as u32) as u16
     }
 
-    fn rand_range(rng: &mut Rng, min: u16, max: u16) -> u16 {
+    fn rand_range<E: Engine>(rng: &mut Rng<E>, min: u16, max: u16) -> u16 {
         rng.rand_range(min as u128, max as u128) as u16
     }
 
-    fn into_randomable(self) -> u16 {
-        self
+    fn rand_range_inclusive<E: Engine>(rng: &mut Rng<E>, min: u16, max: u16) -> u16 {
+        rng.rand_range_inclusive(min as u128, max as u128) as u16
     }
 }
 
 impl Randomable for u32 {
-    fn rand(rng: &mut Rng) -> u32 {
-        let range: u128 = (1 << 32) - 1;
-        rng.rand_range(0, range) as u32
+    fn rand<E: Engine>(rng: &mut Rng<E>) -> u32 {
+        rng.next_u32()
+    }
+}
+
+impl RandomableRange for u32 {
+    const MIN: u32 = u32::MIN;
+    const MAX: u32 = u32::MAX;
+    const ZERO: u32 = 0;
+
+    fn successor(self) -> Option<u32> {
+        self.checked_add(1)
     }
 
-    fn randn(rng: &mut Rng, n: u32) -> u32 {
-        rng.randn(n as u128) as u32
+    fn randn<E: Engine>(rng: &mut Rng<E>, n: u32) -> u32 {
+        rng.randn_u32(n)
     }
 
-    fn rand_range(rng: &mut Rng, min: u32, max: u32) -> u32 {
+    fn rand_range<E: Engine>(rng: &mut Rng<E>, min: u32, max: u32) -> u32 {
         rng.rand_range(min as u128, max as u128) as u32
     }
 
-    fn into_randomable(self) -> u32 {
-        self
+    fn rand_range_inclusive<E: Engine>(rng: &mut Rng<E>, min: u32, max: u32) -> u32 {
+        rng.rand_range_inclusive(min as u128, max as u128) as u32
     }
 }
 
 impl Randomable for u64 {
-    fn rand(rng: &mut Rng) -> u64 {
-        let range: u64 = u64::MAX;
-        rng.rand_range(0, range as u128) as u64
+    fn rand<E: Engine>(rng: &mut Rng<E>) -> u64 {
+        rng.next_u64()
     }
+}
+
+impl RandomableRange for u64 {
+    const MIN: u64 = u64::MIN;
+    const MAX: u64 = u64::MAX;
+    const ZERO: u64 = 0;
 
-    fn randn(rng: &mut Rng, n: u64) -> u64 {
-        rng.randn(n as u128) as u64
+    fn successor(self) -> Option<u64> {
+        self.checked_add(1)
     }
 
-    fn rand_range(rng: &mut Rng, min: u64, max: u64) -> u64 {
+    fn randn<E: Engine>(rng: &mut Rng<E>, n: u64) -> u64 {
+        rng.randn_u64(n)
+    }
+
+    fn rand_range<E: Engine>(rng: &mut Rng<E>, min: u64, max: u64) -> u64 {
         rng.rand_range(min as u128, max as u128) as u64
     }
 
-    fn into_randomable(self) -> u64 {
-        self
+    fn rand_range_inclusive<E: Engine>(rng: &mut Rng<E>, min: u64, max: u64) -> u64 {
+        rng.rand_range_inclusive(min as u128, max as u128) as u64
     }
 }
 
 impl Randomable for u128 {
-    fn rand(rng: &mut Rng) -> u128 {
-        rng.rand_range(0, u128::MAX)
+    fn rand<E: Engine>(rng: &mut Rng<E>) -> u128 {
+        rng.next_u128()
+    }
+}
+
+impl RandomableRange for u128 {
+    const MIN: u128 = u128::MIN;
+    const MAX: u128 = u128::MAX;
+    const ZERO: u128 = 0;
+
+    fn successor(self) -> Option<u128> {
+        self.checked_add(1)
     }
 
-    fn randn(rng: &mut Rng, n: u128) -> u128 {
+    fn randn<E: Engine>(rng: &mut Rng<E>, n: u128) -> u128 {
         rng.randn(n)
     }
 
-    fn rand_range(rng: &mut Rng, min: u128, max: u128) -> u128 {
+    fn rand_range<E: Engine>(rng: &mut Rng<E>, min: u128, max: u128) -> u128 {
         rng.rand_range(min, max)
     }
 
-    fn into_randomable(self) -> u128 {
-        self
+    fn rand_range_inclusive<E: Engine>(rng: &mut Rng<E>, min: u128, max: u128) -> u128 {
+        rng.rand_range_inclusive(min, max)
     }
 }
 
 impl Randomable for usize {
-    fn rand(rng: &mut Rng) -> usize {
-        rng.rand_range(0, usize::MAX as u128) as usize
+    fn rand<E: Engine>(rng: &mut Rng<E>) -> usize {
+        rng.next_u64() as usize
+    }
+}
+
+impl RandomableRange for usize {
+    const MIN: usize = usize::MIN;
+    const MAX: usize = usize::MAX;
+    const ZERO: usize = 0;
+
+    fn successor(self) -> Option<usize> {
+        self.checked_add(1)
     }
 
-    fn randn(rng: &mut Rng, n: usize) -> usize {
+    fn randn<E: Engine>(rng: &mut Rng<E>, n: usize) -> usize {
         rng.randn(n as u128) as usize
     }
 
-    fn rand_range(rng: &mut Rng, min: usize, max: usize) -> usize {
+    fn rand_range<E: Engine>(rng: &mut Rng<E>, min: usize, max: usize) -> usize {
         rng.rand_range(min as u128, max as u128) as usize
     }
 
-    fn into_randomable(self) -> usize {
-        self
+    fn rand_range_inclusive<E: Engine>(rng: &mut Rng<E>, min: usize, max: usize) -> usize {
+        rng.rand_range_inclusive(min as u128, max as u128) as usize
     }
 }
 
 impl Randomable for i8 {
-    fn rand(rng: &mut Rng) -> i8 {
-        rng.rand_range(i8::MIN as u128, i8::MAX as u128) as i8
+    fn rand<E: Engine>(rng: &mut Rng<E>) -> i8 {
+        rng.next_u32() as i8
+    }
+}
+
+impl RandomableRange for i8 {
+    const MIN: i8 = i8::MIN;
+    const MAX: i8 = i8::MAX;
+    const ZERO: i8 = 0;
+
+    fn successor(self) -> Option<i8> {
+        self.checked_add(1)
     }
 
-    fn randn(rng: &mut Rng, n: i8) -> i8 {
-        rng.randn(n as u128) as i8
+    fn randn<E: Engine>(rng: &mut Rng<E>, n: i8) -> i8 {
+        rng.randn_signed(n as i128) as i8
     }
 
-    fn rand_range(rng: &mut Rng, min: i8, max: i8) -> i8 {
-        rng.rand_range(min as u128, max as u128) as i8
+    fn rand_range<E: Engine>(rng: &mut Rng<E>, min: i8, max: i8) -> i8 {
+        rng.rand_range_signed(min as i128, max as i128) as i8
     }
 
-    fn into_randomable(self) -> i8 {
-        self
+    fn rand_range_inclusive<E: Engine>(rng: &mut Rng<E>, min: i8, max: i8) -> i8 {
+        rng.rand_range_inclusive_signed(min as i128, max as i128) as i8
     }
 }
 
 impl Randomable for i16 {
-    fn rand(rng: &mut Rng) -> i16 {
-        rng.rand_range(i16::MIN as u128, i16::MAX as u128) as i16
+    fn rand<E: Engine>(rng: &mut Rng<E>) -> i16 {
+        rng.next_u32() as i16
+    }
+}
+
+impl RandomableRange for i16 {
+    const MIN: i16 = i16::MIN;
+    const MAX: i16 = i16::MAX;
+    const ZERO: i16 = 0;
+
+    fn successor(self) -> Option<i16> {
+        self.checked_add(1)
     }
 
-    fn randn(rng: &mut Rng, n: i16) -> i16 {
-        rng.randn(n as u128) as i16
+    fn randn<E: Engine>(rng: &mut Rng<E>, n: i16) -> i16 {
+        rng.randn_signed(n as i128) as i16
     }
 
-    fn rand_range(rng: &mut Rng, min: i16, max: i16) -> i16 {
-        rng.rand_range(min as u128, max as u128) as i16
+    fn rand_range<E: Engine>(rng: &mut Rng<E>, min: i16, max: i16) -> i16 {
+        rng.rand_range_signed(min as i128, max as i128) as i16
     }
 
-    fn into_randomable(self) -> i16 {
-        self
+    fn rand_range_inclusive<E: Engine>(rng: &mut Rng<E>, min: i16, max: i16) -> i16 {
+        rng.rand_range_inclusive_signed(min as i128, max as i128) as i16
     }
 }
 
 impl Randomable for i32 {
-    fn rand(rng: &mut Rng) -> i32 {
-        rng.rand_range(i32::MIN as u128, i32::MAX as u128) as i32
+    fn rand<E: Engine>(rng: &mut Rng<E>) -> i32 {
+        rng.next_u32() as i32
+    }
+}
+
+impl RandomableRange for i32 {
+    const MIN: i32 = i32::MIN;
+    const MAX: i32 = i32::MAX;
+    const ZERO: i32 = 0;
+
+    fn successor(self) -> Option<i32> {
+        self.checked_add(1)
     }
 
-    fn randn(rng: &mut Rng, n: i32) -> i32 {
-        rng.randn(n as u128) as i32
+    fn randn<E: Engine>(rng: &mut Rng<E>, n: i32) -> i32 {
+        rng.randn_signed(n as i128) as i32
     }
 
-    fn rand_range(rng: &mut Rng, min: i32, max: i32) -> i32 {
-        rng.rand_range(min as u128, max as u128) as i32
+    fn rand_range<E: Engine>(rng: &mut Rng<E>, min: i32, max: i32) -> i32 {
+        rng.rand_range_signed(min as i128, max as i128) as i32
     }
 
-    fn into_randomable(self) -> i32 {
-        self
+    fn rand_range_inclusive<E: Engine>(rng: &mut Rng<E>, min: i32, max: i32) -> i32 {
+        rng.rand_range_inclusive_signed(min as i128, max as i128) as i32
     }
 }
 
 impl Randomable for i64 {
-    fn rand(rng: &mut Rng) -> i64 {
-        rng.rand_range(i64::MIN as u128, i64::MAX as u128) as i64
+    fn rand<E: Engine>(rng: &mut Rng<E>) -> i64 {
+        rng.next_u64() as i64
     }
+}
+
+impl RandomableRange for i64 {
+    const MIN: i64 = i64::MIN;
+    const MAX: i64 = i64::MAX;
+    const ZERO: i64 = 0;
 
-    fn randn(rng: &mut Rng, n: i64) -> i64 {
-        rng.randn(n as u128) as i64
+    fn successor(self) -> Option<i64> {
+        self.checked_add(1)
     }
 
-    fn rand_range(rng: &mut Rng, min: i64, max: i64) -> i64 {
-        rng.rand_range(min as u128, max as u128) as i64
+    fn randn<E: Engine>(rng: &mut Rng<E>, n: i64) -> i64 {
+        rng.randn_signed(n as i128) as i64
     }
 
-    fn into_randomable(self) -> i64 {
-        self
+    fn rand_range<E: Engine>(rng: &mut Rng<E>, min: i64, max: i64) -> i64 {
+        rng.rand_range_signed(min as i128, max as i128) as i64
+    }
+
+    fn rand_range_inclusive<E: Engine>(rng: &mut Rng<E>, min: i64, max: i64) -> i64 {
+        rng.rand_range_inclusive_signed(min as i128, max as i128) as i64
     }
 }
 
 impl Randomable for i128 {
-    fn rand(rng: &mut Rng) -> i128 {
-        rng.rand_range(i128::MIN as u128, i128::MAX as u128) as i128
+    fn rand<E: Engine>(rng: &mut Rng<E>) -> i128 {
+        rng.next_u128() as i128
     }
+}
 
-    fn randn(rng: &mut Rng, n: i128) -> i128 {
-        rng.randn(n as u128) as i128
+impl RandomableRange for i128 {
+    const MIN: i128 = i128::MIN;
+    const MAX: i128 = i128::MAX;
+    const ZERO: i128 = 0;
+
+    fn successor(self) -> Option<i128> {
+        self.checked_add(1)
+    }
+
+    fn randn<E: Engine>(rng: &mut Rng<E>, n: i128) -> i128 {
+        rng.randn_signed(n)
     }
 
-    fn rand_range(rng: &mut Rng, min: i128, max: i128) -> i128 {
-        rng.rand_range(min as u128, max as u128) as i128
+    fn rand_range<E: Engine>(rng: &mut Rng<E>, min: i128, max: i128) -> i128 {
+        rng.rand_range_signed(min, max)
     }
 
-    fn into_randomable(self) -> i128 {
-        self
+    fn rand_range_inclusive<E: Engine>(rng: &mut Rng<E>, min: i128, max: i128) -> i128 {
+        rng.rand_range_inclusive_signed(min, max)
     }
 }
 
 impl Randomable for isize {
-    fn rand(rng: &mut Rng) -> isize {
-        rng.rand_range(isize::MIN as u128, isize::MAX as u128) as isize
+    fn rand<E: Engine>(rng: &mut Rng<E>) -> isize {
+        rng.next_u64() as isize
+    }
+}
+
+impl RandomableRange for isize {
+    const MIN: isize = isize::MIN;
+    const MAX: isize = isize::MAX;
+    const ZERO: isize = 0;
+
+    fn successor(self) -> Option<isize> {
+        self.checked_add(1)
     }
 
-    fn randn(rng: &mut Rng, n: isize) -> isize {
-        rng.randn(n as u128) as isize
+    fn randn<E: Engine>(rng: &mut Rng<E>, n: isize) -> isize {
+        rng.randn_signed(n as i128) as isize
     }
 
-    fn rand_range(rng: &mut Rng, min: isize, max: isize) -> isize {
-        rng.rand_range(min as u128, max as u128) as isize
+    fn rand_range<E: Engine>(rng: &mut Rng<E>, min: isize, max: isize) -> isize {
+        rng.rand_range_signed(min as i128, max as i128) as isize
     }
 
-    fn into_randomable(self) -> isize {
-        self
+    fn rand_range_inclusive<E: Engine>(rng: &mut Rng<E>, min: isize, max: isize) -> isize {
+        rng.rand_range_inclusive_signed(min as i128, max as i128) as isize
     }
 }
 
 impl Randomable for f32 {
-    fn rand(rng: &mut Rng) -> f32 {
-        rng.rand_range(0, u32::MAX as u128) as f32 / u32::MAX as f32
+    fn rand<E: Engine>(rng: &mut Rng<E>) -> f32 {
+        rng.next_f32()
     }
+}
 
-    fn randn(rng: &mut Rng, n: f32) -> f32 {
-        (rng.randn((n * u32::MAX as f32) as u128) as f32) / u32::MAX as f32
+impl RandomableRange for f32 {
+    const MIN: f32 = f32::MIN;
+    const MAX: f32 = f32::MAX;
+    const ZERO: f32 = 0.0;
+
+    // The next representable value, non-finite values are left for `check_range`
+    fn successor(self) -> Option<f32> {
+        if !self.is_finite() {
+            return Some(self);
+        }
+        if self == f32::MAX {
+            return None;
+        }
+        let bits = self.to_bits();
+        let next = if self == 0.0 {
+            1
+        } else if self > 0.0 {
+            bits + 1
+        } else {
+            bits - 1
+        };
+        Some(f32::from_bits(next))
     }
 
-    fn rand_range(rng: &mut Rng, min: f32, max: f32) -> f32 {
-        let range = max - min;
-        min + (rng.rand_range(0, u32::MAX as u128) as f32 / u32::MAX as f32) * range
+    fn randn<E: Engine>(rng: &mut Rng<E>, n: f32) -> f32 {
+        rng.rand_range_f32(0.0, n)
     }
 
-    fn into_randomable(self) -> f32 {
-        self
+    fn rand_range<E: Engine>(rng: &mut Rng<E>, min: f32, max: f32) -> f32 {
+        rng.rand_range_f32(min, max)
+    }
+
+    fn rand_range_inclusive<E: Engine>(rng: &mut Rng<E>, min: f32, max: f32) -> f32 {
+        rng.rand_range_inclusive_f32(min, max)
+    }
+
+    fn check_range(min: f32, max: f32) -> Result<(), Error> {
+        if !min.is_finite() || !max.is_finite() {
+            Err(Error::NonFiniteFloat)
+        } else if min < max {
+            Ok(())
+        } else {
+            Err(Error::EmptyRange)
+        }
     }
 }
 
 impl Randomable for f64 {
-    fn rand(rng: &mut Rng) -> f64 {
-        rng.rand_range(0, u64::MAX as u128) as f64 / u64::MAX as f64
+    fn rand<E: Engine>(rng: &mut Rng<E>) -> f64 {
+        rng.next_f64()
+    }
+}
+
+impl RandomableRange for f64 {
+    const MIN: f64 = f64::MIN;
+    const MAX: f64 = f64::MAX;
+    const ZERO: f64 = 0.0;
+
+    // The next representable value, non-finite values are left for `check_range`
+    fn successor(self) -> Option<f64> {
+        if !self.is_finite() {
+            return Some(self);
+        }
+        if self == f64::MAX {
+            return None;
+        }
+        let bits = self.to_bits();
+        let next = if self == 0.0 {
+            1
+        } else if self > 0.0 {
+            bits + 1
+        } else {
+            bits - 1
+        };
+        Some(f64::from_bits(next))
+    }
+
+    fn randn<E: Engine>(rng: &mut Rng<E>, n: f64) -> f64 {
+        rng.rand_range_f64(0.0, n)
+    }
+
+    fn rand_range<E: Engine>(rng: &mut Rng<E>, min: f64, max: f64) -> f64 {
+        rng.rand_range_f64(min, max)
+    }
+
+    fn rand_range_inclusive<E: Engine>(rng: &mut Rng<E>, min: f64, max: f64) -> f64 {
+        rng.rand_range_inclusive_f64(min, max)
+    }
+
+    fn check_range(min: f64, max: f64) -> Result<(), Error> {
+        if !min.is_finite() || !max.is_finite() {
+            Err(Error::NonFiniteFloat)
+        } else if min < max {
+            Ok(())
+        } else {
+            Err(Error::EmptyRange)
+        }
+    }
+}
+
+impl Randomable for bool {
+    fn rand<E: Engine>(rng: &mut Rng<E>) -> bool {
+        // The top bit, the low ones of some engines are weak
+        rng.next_u32() >> 31 == 1
+    }
+}
+
+const SURROGATE_START: u32 = 0xD800;
+const SURROGATES: u32 = 0x800;
+
+// Unicode scalar values without the surrogate gap, so ranges of chars map to ranges of u32
+fn char_to_index(c: char) -> u32 {
+    let c = c as u32;
+    if c >= SURROGATE_START {
+        c - SURROGATES
+    } else {
+        c
+    }
+}
+
+fn index_to_char(index: u32) -> char {
+    let c = if index >= SURROGATE_START {
+        index + SURROGATES
+    } else {
+        index
+    };
+    char::from_u32(c).unwrap()
+}
+
+impl Randomable for char {
+    fn rand<E: Engine>(rng: &mut Rng<E>) -> char {
+        index_to_char(rng.randn_u32(char_to_index(char::MAX) + 1))
+    }
+}
+
+impl RandomableRange for char {
+    const MIN: char = '\0';
+    const MAX: char = char::MAX;
+    const ZERO: char = '\0';
+
+    fn successor(self) -> Option<char> {
+        if self == char::MAX {
+            return None;
+        }
+        Some(index_to_char(char_to_index(self) + 1))
     }
 
-    fn randn(rng: &mut Rng, n: f64) -> f64 {
-        (rng.randn((n * u64::MAX as f64) as u128) as f64) / u64::MAX as f64
+    fn randn<E: Engine>(rng: &mut Rng<E>, n: char) -> char {
+        Self::rand_range(rng, '\0', n)
     }
 
-    fn rand_range(rng: &mut Rng, min: f64, max: f64) -> f64 {
-        let range = max - min;
-        min + (rng.rand_range(0, u64::MAX as u128) as f64 / u64::MAX as f64) * range
+    fn rand_range<E: Engine>(rng: &mut Rng<E>, min: char, max: char) -> char {
+        let index = rng.rand_range(char_to_index(min) as u128, char_to_index(max) as u128);
+        index_to_char(index as u32)
     }
 
-    fn into_randomable(self) -> f64 {
-        self
+    fn rand_range_inclusive<E: Engine>(rng: &mut Rng<E>, min: char, max: char) -> char {
+        let index =
+            rng.rand_range_inclusive(char_to_index(min) as u128, char_to_index(max) as u128);
+        index_to_char(index as u32)
     }
 }
 
@@ -340,11 +920,7 @@ impl Randomable for f64 {
 mod tests {
     use super::*;
 
-    fn test_random_range<T: Randomable + PartialOrd + std::fmt::Debug + Copy>(
-        rng: &mut Rng,
-        min: T,
-        max: T,
-    ) {
+    fn test_random_range<T: RandomableRange + std::fmt::Debug>(rng: &mut Rng, min: T, max: T) {
         for _ in 0..100 {
             let value = T::rand_range(rng, min, max);
             assert!(
@@ -357,19 +933,30 @@ mod tests {
         }
     }
 
-    fn test_randomn<T: Randomable + PartialOrd + std::fmt::Debug + Copy>(rng: &mut Rng, n: T) {
+    fn test_randomn<T: RandomableRange + std::fmt::Debug>(rng: &mut Rng, n: T) {
         for _ in 0..100 {
             let value = T::randn(rng, n);
             assert!(value < n, "Value {:?} is not less than {:?}", value, n);
         }
     }
 
+    #[test]
+    fn test_new_rngs_differ() {
+        let mut a = Rng::new();
+        let mut b = Rng::new();
+        assert_eq!(a.get_seed(), 0);
+        assert_ne!(a.rand(), b.rand());
+    }
+
     #[test]
     fn test_u64_randomable() {
         let mut rng = Rng::new();
         rng.set_seed(1);
         let value = u64::rand(&mut rng);
-        assert_eq!(value, 1103527590, "u64::rand() generated a negative value");
+        assert_eq!(
+            value, 14435815681772820999,
+            "u64::rand() generated a negative value"
+        );
 
         test_random_range(&mut rng, 10u64, 100u64);
         test_randomn(&mut rng, 50u64);
@@ -380,7 +967,10 @@ mod tests {
         let mut rng = Rng::new();
         rng.set_seed(1);
         let value = u128::rand(&mut rng);
-        assert_eq!(value, 1103527590, "u128::rand() generated a negative value");
+        assert_eq!(
+            value, 287020114512883496188310118456209250823,
+            "u128::rand() generated a negative value"
+        );
 
         test_random_range(&mut rng, 100u128, 1000u128);
         test_randomn(&mut rng, 500u128);
@@ -392,7 +982,7 @@ mod tests {
         rng.set_seed(1);
         let value = usize::rand(&mut rng);
         assert_eq!(
-            value, 1103527590,
+            value, 14435815681772820999,
             "usize::rand() generated a negative value"
         );
 
@@ -400,20 +990,197 @@ mod tests {
         test_randomn(&mut rng, 15usize);
     }
 
+    #[test]
+    fn test_full_width() {
+        let mut rng = Rng::new();
+        let mut u128_top = 0;
+        let mut u64_top = 0;
+        let mut range_top = 0;
+        for _ in 0..1000 {
+            u128_top += (u128::rand(&mut rng) >> 127) as usize;
+            u64_top += (u64::rand(&mut rng) >> 63) as usize;
+            range_top += (u128::rand_range(&mut rng, 0, u128::MAX) >> 127) as usize;
+        }
+        assert!((400..600).contains(&u128_top), "{}", u128_top);
+        assert!((400..600).contains(&u64_top), "{}", u64_top);
+        assert!((400..600).contains(&range_top), "{}", range_top);
+    }
+
+    #[test]
+    fn test_randn_above_63_bits() {
+        let mut rng = Rng::new();
+        let n = (1u128 << 100) + 12345;
+        let mut above = 0;
+        for _ in 0..1000 {
+            let value = rng.randn(n);
+            assert!(value < n);
+            above += (value >= n / 2) as usize;
+        }
+        assert!((400..600).contains(&above), "{}", above);
+    }
+
+    // Replays fixed outputs so the rejection paths can be checked exactly
+    struct Replay(Vec<u64>);
+
+    impl Engine for Replay {
+        fn from_seed(_seed: u128) -> Replay {
+            Replay(Vec::new())
+        }
+
+        fn next_u32(&mut self) -> u32 {
+            self.0.remove(0) as u32
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            self.0.remove(0)
+        }
+    }
+
+    #[test]
+    fn test_lemire_rejection() {
+        // 2^32 % 3 == 1, so only a zero low word is rejected
+        let mut rng = Rng::from_engine(Replay(vec![0, 1 << 31]));
+        assert_eq!(rng.randn_u32(3), 1);
+        assert!(rng.engine.0.is_empty());
+
+        let mut rng = Rng::from_engine(Replay(vec![0, u64::MAX]));
+        assert_eq!(rng.randn_u64(3), 2);
+        assert!(rng.engine.0.is_empty());
+
+        // Nothing is rejected for powers of two
+        let mut rng = Rng::from_engine(Replay(vec![0]));
+        assert_eq!(rng.randn_u32(1 << 20), 0);
+    }
+
+    #[test]
+    fn test_lemire_uniform() {
+        let mut rng: Rng = Rng::with_seed(11);
+
+        // Modulo on 32 bits would put half of the draws into the first third
+        let n = 3 << 30;
+        let mut thirds = [0u32; 3];
+        for _ in 0..30_000 {
+            thirds[(rng.randn_u32(n) >> 30) as usize] += 1;
+        }
+        for count in thirds.iter() {
+            assert!((9_500..10_500).contains(count), "{:?}", thirds);
+        }
+
+        let n = 3 << 62;
+        let mut thirds = [0u32; 3];
+        for _ in 0..30_000 {
+            thirds[(rng.randn_u64(n) >> 62) as usize] += 1;
+        }
+        for count in thirds.iter() {
+            assert!((9_500..10_500).contains(count), "{:?}", thirds);
+        }
+
+        let n = 3 << 126;
+        let mut thirds = [0u32; 3];
+        for _ in 0..30_000 {
+            thirds[(rng.randn(n) >> 126) as usize] += 1;
+        }
+        for count in thirds.iter() {
+            assert!((9_500..10_500).contains(count), "{:?}", thirds);
+        }
+    }
+
+    #[test]
+    fn test_randn_bounds_at_widths() {
+        let mut rng = Rng::new();
+        for n in [
+            1,
+            2,
+            u32::MAX as u128,
+            u32::MAX as u128 + 1,
+            u64::MAX as u128,
+            u64::MAX as u128 + 1,
+            u128::MAX,
+        ]
+        .iter()
+        {
+            for _ in 0..100 {
+                assert!(rng.randn(*n) < *n);
+            }
+        }
+    }
+
+    #[test]
+    fn test_i8_exhaustive_ranges() {
+        let mut rng = Rng::new();
+        for min in i8::MIN..i8::MAX {
+            for max in min + 1..=i8::MAX {
+                test_random_range(&mut rng, min, max);
+            }
+        }
+
+        let mut seen = [false; 256];
+        for _ in 0..10_000 {
+            seen[(i8::rand(&mut rng) as u8) as usize] = true;
+        }
+        assert!(seen.iter().all(|s| *s), "i8::rand() missed a value");
+    }
+
+    #[test]
+    fn test_i16_full_span() {
+        let mut rng = Rng::new();
+        let mut seen = vec![false; 1 << 16];
+        for _ in 0..2_000_000 {
+            seen[(i16::rand_range(&mut rng, i16::MIN, i16::MAX) as u16) as usize] = true;
+        }
+        let missed: Vec<usize> = (0..1 << 16).filter(|i| !seen[*i]).collect();
+        assert_eq!(missed, vec![i16::MAX as u16 as usize]);
+
+        for min in (i16::MIN..i16::MAX).step_by(251) {
+            for max in (min + 1..=i16::MAX).step_by(509) {
+                test_random_range(&mut rng, min, max);
+            }
+        }
+    }
+
+    #[test]
+    fn test_signed_randn() {
+        let mut rng = Rng::new();
+        test_randomn(&mut rng, 1i8);
+        test_randomn(&mut rng, i8::MAX);
+        test_randomn(&mut rng, i64::MAX);
+        test_randomn(&mut rng, i128::MAX);
+        for _ in 0..100 {
+            assert!(i32::randn(&mut rng, 10) >= 0);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "must be bigger than 0")]
+    fn test_signed_randn_negative() {
+        i32::randn(&mut Rng::new(), -5);
+    }
+
+    #[test]
+    fn test_signed_full_range() {
+        let mut rng = Rng::new();
+        test_random_range(&mut rng, i128::MIN, i128::MAX);
+        test_random_range(&mut rng, i64::MIN, i64::MAX);
+        test_random_range(&mut rng, -5i32, 5i32);
+
+        let negative = (0..1000).filter(|_| i64::rand(&mut rng) < 0).count();
+        assert!((400..600).contains(&negative), "{}", negative);
+    }
+
     #[test]
     fn test_f32_randomable() {
         let mut rng = Rng::new();
         for _ in 0..100 {
             let value = f32::rand(&mut rng);
             assert!(
-                value >= 0.0 && value <= 1.0,
+                (0.0..1.0).contains(&value),
                 "f32::rand() generated out of range: {}",
                 value
             );
 
             let range_value = f32::rand_range(&mut rng, 0.0, 10.0);
             assert!(
-                range_value >= 0.0 && range_value < 10.0,
+                (0.0..10.0).contains(&range_value),
                 "f32::rand_range() generated out of range: {}",
                 range_value
             );
@@ -433,14 +1200,14 @@ mod tests {
         for _ in 0..100 {
             let value = f64::rand(&mut rng);
             assert!(
-                value >= 0.0 && value <= 1.0,
+                (0.0..1.0).contains(&value),
                 "f64::rand() generated out of range: {}",
                 value
             );
 
             let range_value = f64::rand_range(&mut rng, 0.0, 10.0);
             assert!(
-                range_value >= 0.0 && range_value < 10.0,
+                (0.0..10.0).contains(&range_value),
                 "f64::rand_range() generated out of range: {}",
                 range_value
             );
@@ -453,4 +1220,333 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_float_mantissa_bits() {
+        let mut rng = Rng::new();
+        for _ in 0..1000 {
+            let v = rng.next_f64() * (1u64 << 53) as f64;
+            assert_eq!(v, v.trunc());
+            let v = rng.next_f32() * (1u32 << 24) as f32;
+            assert_eq!(v, v.trunc());
+        }
+
+        // The lowest bits have to be exercised too
+        let odd = (0..1000)
+            .filter(|_| ((rng.next_f64() * (1u64 << 53) as f64) as u64) & 1 == 1)
+            .count();
+        assert!((400..600).contains(&odd), "{}", odd);
+    }
+
+    #[test]
+    fn test_float_uniform() {
+        let mut rng: Rng = Rng::with_seed(5);
+        let mut buckets = [0u32; 10];
+        for _ in 0..100_000 {
+            buckets[(f64::rand(&mut rng) * 10.0) as usize] += 1;
+        }
+        for count in buckets.iter() {
+            assert!((9_500..10_500).contains(count), "{:?}", buckets);
+        }
+
+        let mut buckets = [0u32; 10];
+        for _ in 0..100_000 {
+            buckets[(f32::rand_range(&mut rng, -2.5, 2.5) * 2.0 + 5.0) as usize] += 1;
+        }
+        for count in buckets.iter() {
+            assert!((9_500..10_500).contains(count), "{:?}", buckets);
+        }
+    }
+
+    #[test]
+    fn test_float_ranges() {
+        let mut rng = Rng::new();
+        for _ in 0..1000 {
+            test_random_range(&mut rng, 0.25f64, 0.5f64);
+            test_random_range(&mut rng, -0.75f32, -0.5f32);
+            test_random_range(&mut rng, -30000.1f64, -10.1f64);
+            test_random_range(&mut rng, 1.0f32, 1.0000001f32);
+            test_random_range(&mut rng, f64::MIN, f64::MAX);
+            test_random_range(&mut rng, f32::MIN, f32::MAX);
+            test_randomn(&mut rng, 0.001f64);
+        }
+
+        // A fractional span must not be truncated to a whole number
+        let fractional = (0..100)
+            .map(|_| f64::rand_range(&mut rng, 0.1, 0.9))
+            .filter(|v| *v != 0.1)
+            .count();
+        assert_eq!(fractional, 100);
+
+        let negative = (0..1000)
+            .filter(|_| f64::rand_range(&mut rng, f64::MIN, f64::MAX) < 0.0)
+            .count();
+        assert!((400..600).contains(&negative), "{}", negative);
+    }
+
+    #[test]
+    #[should_panic(expected = "bounds must be finite")]
+    fn test_float_range_infinite() {
+        f64::rand_range(&mut Rng::new(), 0.0, f64::INFINITY);
+    }
+
+    #[test]
+    #[should_panic(expected = "bounds must be finite")]
+    fn test_float_range_nan() {
+        f32::rand_range(&mut Rng::new(), f32::NAN, 1.0);
+    }
+
+    #[test]
+    fn test_gen_range_forms() {
+        let mut rng = Rng::new();
+        let mut seen = [false; 256];
+        for _ in 0..10_000 {
+            seen[u8::gen_range(&mut rng, 0..=u8::MAX) as usize] = true;
+        }
+        assert!(seen.iter().all(|s| *s), "0..=255 missed a value");
+
+        for _ in 0..1000 {
+            assert!(u8::gen_range(&mut rng, ..=3) <= 3);
+            assert!(u8::gen_range(&mut rng, ..3) < 3);
+            assert!(u8::gen_range(&mut rng, 250..) >= 250);
+            assert!((-3..=3).contains(&i16::gen_range(&mut rng, -3..=3)));
+            assert!(i8::gen_range(&mut rng, ..-100) < -100);
+            assert!((0.5..=0.75).contains(&f64::gen_range(&mut rng, 0.5..=0.75)));
+            assert!((-1.0..0.0).contains(&f32::gen_range(&mut rng, -1.0..0.0)));
+        }
+
+        let top = (0..1000)
+            .filter(|_| u8::gen_range(&mut rng, 250..) == 255)
+            .count();
+        assert!(top > 100, "{}", top);
+    }
+
+    #[test]
+    fn test_gen_range_full_domain() {
+        let mut rng = Rng::new();
+        let high = (0..1000)
+            .filter(|_| u64::gen_range(&mut rng, 0..=u64::MAX) >> 63 == 1)
+            .count();
+        assert!((400..600).contains(&high), "{}", high);
+
+        let negative = (0..1000)
+            .filter(|_| i128::gen_range(&mut rng, ..) < 0)
+            .count();
+        assert!((400..600).contains(&negative), "{}", negative);
+
+        u128::gen_range(&mut rng, 0..=u128::MAX);
+        i64::gen_range(&mut rng, i64::MIN..=i64::MAX);
+    }
+
+    #[test]
+    fn test_gen_range_single_value() {
+        let mut rng = Rng::new();
+        assert_eq!(u32::gen_range(&mut rng, 7..=7), 7);
+        assert_eq!(i128::gen_range(&mut rng, i128::MIN..=i128::MIN), i128::MIN);
+        assert_eq!(f64::gen_range(&mut rng, 1.5..=1.5), 1.5);
+    }
+
+    #[test]
+    #[should_panic(expected = "max must be bigger than min")]
+    fn test_swapped_bounds() {
+        u32::rand_range(&mut Rng::new(), 10, 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "max must not be smaller than min")]
+    fn test_swapped_inclusive_bounds() {
+        #[allow(clippy::reversed_empty_ranges)]
+        u64::gen_range(&mut Rng::new(), 10..=5);
+    }
+
+    #[test]
+    fn test_try_errors() {
+        let mut rng = Rng::new();
+        assert_eq!(rng.try_randn(0), Err(Error::EmptyRange));
+        assert_eq!(rng.try_rand_range(5, 5), Err(Error::EmptyRange));
+        assert_eq!(rng.try_rand_range(6, 5), Err(Error::EmptyRange));
+        assert!(rng.try_randn(u128::MAX).is_ok());
+
+        assert_eq!(u8::try_randn(&mut rng, 0), Err(Error::EmptyRange));
+        assert_eq!(i32::try_randn(&mut rng, -4), Err(Error::InvalidBound));
+        assert_eq!(i64::try_rand_range(&mut rng, 3, -3), Err(Error::EmptyRange));
+        assert_eq!(f64::try_randn(&mut rng, -1.0), Err(Error::InvalidBound));
+        assert_eq!(
+            f64::try_randn(&mut rng, f64::NAN),
+            Err(Error::NonFiniteFloat)
+        );
+        assert_eq!(
+            f32::try_rand_range(&mut rng, 0.0, f32::INFINITY),
+            Err(Error::NonFiniteFloat)
+        );
+        assert_eq!(
+            f32::try_rand_range(&mut rng, 1.0, 1.0),
+            Err(Error::EmptyRange)
+        );
+    }
+
+    #[test]
+    fn test_try_gen_range() {
+        let mut rng = Rng::new();
+        #[allow(clippy::reversed_empty_ranges)]
+        let swapped = u64::try_gen_range(&mut rng, 10..=5);
+        assert_eq!(swapped, Err(Error::EmptyRange));
+        assert_eq!(i8::try_gen_range(&mut rng, 3..3), Err(Error::EmptyRange));
+        assert_eq!(i8::try_gen_range(&mut rng, 3..=3), Ok(3));
+        assert_eq!(u8::try_gen_range(&mut rng, ..0), Err(Error::EmptyRange));
+        assert_eq!(
+            f64::try_gen_range(&mut rng, 0.0..=f64::NAN),
+            Err(Error::NonFiniteFloat)
+        );
+        assert_eq!(
+            f32::try_gen_range(&mut rng, f32::INFINITY..),
+            Err(Error::NonFiniteFloat)
+        );
+        for _ in 0..100 {
+            let value = rng.try_gen_range(-5i32..=5).unwrap();
+            assert!((-5..=5).contains(&value), "{}", value);
+            let value = char::try_gen_range(&mut rng, 'a'..'c').unwrap();
+            assert!(value == 'a' || value == 'b', "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_gen_range_excluded_start() {
+        let mut rng = Rng::new();
+        let mut seen = [false; 3];
+        for _ in 0..1_000 {
+            let value = u8::gen_range(&mut rng, (Bound::Excluded(5), Bound::Included(7)));
+            seen[value as usize - 5] = true;
+        }
+        assert_eq!(seen, [false, true, true]);
+
+        assert_eq!(
+            i32::gen_range(&mut rng, (Bound::Excluded(-2), Bound::Excluded(0))),
+            -1
+        );
+        assert_eq!(
+            char::gen_range(
+                &mut rng,
+                (Bound::Excluded('\u{D7FF}'), Bound::Included('\u{E000}'))
+            ),
+            '\u{E000}'
+        );
+        let value = f64::gen_range(&mut rng, (Bound::Excluded(0.0), Bound::Excluded(1e-300)));
+        assert!(value > 0.0 && value < 1e-300, "{}", value);
+
+        assert_eq!(0.0f64.successor(), Some(f64::from_bits(1)));
+        assert_eq!((-0.0f32).successor(), Some(f32::from_bits(1)));
+        assert_eq!((-1.0f64).successor(), Some(-1.0 + f64::EPSILON / 2.0));
+        assert_eq!(1.0f32.successor(), Some(1.0 + f32::EPSILON));
+        assert_eq!(f64::MAX.successor(), None);
+
+        // Nothing is above the top of the domain
+        assert_eq!(
+            u8::try_gen_range(&mut rng, (Bound::Excluded(u8::MAX), Bound::Unbounded)),
+            Err(Error::EmptyRange)
+        );
+        assert_eq!(
+            f32::try_gen_range(&mut rng, (Bound::Excluded(1.0), Bound::Included(1.0))),
+            Err(Error::EmptyRange)
+        );
+        assert_eq!(
+            f64::try_gen_range(&mut rng, (Bound::Excluded(f64::NAN), Bound::Unbounded)),
+            Err(Error::NonFiniteFloat)
+        );
+        assert_eq!(
+            i64::try_gen_range(&mut rng, (Bound::Excluded(i64::MAX - 1), Bound::Unbounded)),
+            Ok(i64::MAX)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "nothing is above the excluded start bound")]
+    fn test_gen_range_excluded_max() {
+        i16::gen_range(
+            &mut Rng::new(),
+            (Bound::Excluded(i16::MAX), Bound::Unbounded),
+        );
+    }
+
+    #[test]
+    fn test_try_gen_bool() {
+        let mut rng = Rng::new();
+        assert_eq!(rng.try_gen_bool(1.5), Err(Error::InvalidParameter));
+        assert_eq!(rng.try_gen_bool(-0.1), Err(Error::InvalidParameter));
+        assert_eq!(rng.try_gen_bool(f64::NAN), Err(Error::NonFiniteFloat));
+        assert_eq!(rng.try_gen_bool(1.0), Ok(true));
+        assert_eq!(rng.try_gen_bool(0.0), Ok(false));
+    }
+
+    #[test]
+    fn test_try_values() {
+        let mut rng = Rng::new();
+        for _ in 0..100 {
+            let value = i16::try_rand_range(&mut rng, -10, 10).unwrap();
+            assert!((-10..10).contains(&value), "{}", value);
+            let value = u64::try_randn(&mut rng, 3).unwrap();
+            assert!(value < 3, "{}", value);
+            let value = f64::try_rand_range(&mut rng, -0.5, 0.5).unwrap();
+            assert!((-0.5..0.5).contains(&value), "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_bool() {
+        let mut rng = Rng::new();
+        let heads = (0..10_000).filter(|_| bool::rand(&mut rng)).count();
+        assert!((4_800..5_200).contains(&heads), "{}", heads);
+
+        let hits = (0..10_000).filter(|_| rng.gen_bool(0.1)).count();
+        assert!((850..1_150).contains(&hits), "{}", hits);
+        assert!((0..1000).all(|_| rng.gen_bool(1.0)));
+        assert!((0..1000).all(|_| !rng.gen_bool(0.0)));
+    }
+
+    #[test]
+    #[should_panic(expected = "p must be between 0 and 1")]
+    fn test_gen_bool_invalid() {
+        Rng::new().gen_bool(1.5);
+    }
+
+    #[test]
+    fn test_char_index() {
+        assert_eq!(char_to_index('\u{D7FF}') + 1, char_to_index('\u{E000}'));
+        assert_eq!(index_to_char(char_to_index('\u{E000}')), '\u{E000}');
+        assert_eq!(index_to_char(char_to_index(char::MAX)), char::MAX);
+        for index in [0, 0xD7FF, 0xD800, 0x10FFFF - 0x800].iter() {
+            assert_eq!(char_to_index(index_to_char(*index)), *index);
+        }
+    }
+
+    #[test]
+    fn test_char() {
+        let mut rng = Rng::new();
+        let mut astral = 0;
+        for _ in 0..10_000 {
+            let c = char::rand(&mut rng);
+            astral += (c as u32 > 0xFFFF) as usize;
+        }
+        // 16 of the 17 planes are above the BMP
+        assert!(astral > 9_000, "{}", astral);
+
+        let mut seen = [false; 26];
+        for _ in 0..10_000 {
+            let c = char::gen_range(&mut rng, 'a'..='z');
+            assert!(c.is_ascii_lowercase(), "{}", c);
+            seen[(c as u8 - b'a') as usize] = true;
+        }
+        assert!(seen.iter().all(|s| *s));
+
+        for _ in 0..1000 {
+            let c = char::rand_range(&mut rng, '\u{D7FE}', '\u{E002}');
+            assert!(
+                matches!(c as u32, 0xD7FE | 0xD7FF | 0xE000 | 0xE001),
+                "{:?}",
+                c
+            );
+            assert!(char::randn(&mut rng, 'A') < 'A');
+        }
+        assert_eq!(char::gen_range(&mut rng, 'x'..='x'), 'x');
+    }
 }
@@ -0,0 +1,232 @@
+use crate::engine::Engine;
+use crate::entropy;
+use crate::error::Error;
+use crate::seed::expand_seed;
+use std::fmt;
+
+// "expand 32-byte k"
+const CONSTANTS: [u32; 4] = [0x61707865, 0x3320646e, 0x79622d32, 0x6b206574];
+const BLOCK_WORDS: usize = 16;
+
+// https://cr.yp.to/chacha/chacha-20080128.pdf, with a 64-bit block counter and a 64-bit stream id
+#[derive(Clone)]
+struct Core {
+    key: [u32; 8],
+    counter: u64,
+    stream: u64,
+    block: [u32; BLOCK_WORDS],
+    index: usize,
+    double_rounds: usize,
+}
+
+impl Core {
+    fn new(key: [u8; 32], stream: u64, rounds: usize) -> Core {
+        let mut words = [0u32; 8];
+        for (word, bytes) in words.iter_mut().zip(key.chunks(4)) {
+            *word = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        }
+        Core {
+            key: words,
+            counter: 0,
+            stream,
+            block: [0; BLOCK_WORDS],
+            index: BLOCK_WORDS,
+            double_rounds: rounds / 2,
+        }
+    }
+
+    fn refill(&mut self) {
+        let mut input = [0u32; BLOCK_WORDS];
+        input[..4].copy_from_slice(&CONSTANTS);
+        input[4..12].copy_from_slice(&self.key);
+        input[12] = self.counter as u32;
+        input[13] = (self.counter >> 32) as u32;
+        input[14] = self.stream as u32;
+        input[15] = (self.stream >> 32) as u32;
+
+        let mut x = input;
+        for _ in 0..self.double_rounds {
+            quarter_round(&mut x, 0, 4, 8, 12);
+            quarter_round(&mut x, 1, 5, 9, 13);
+            quarter_round(&mut x, 2, 6, 10, 14);
+            quarter_round(&mut x, 3, 7, 11, 15);
+            quarter_round(&mut x, 0, 5, 10, 15);
+            quarter_round(&mut x, 1, 6, 11, 12);
+            quarter_round(&mut x, 2, 7, 8, 13);
+            quarter_round(&mut x, 3, 4, 9, 14);
+        }
+        for (out, (mixed, original)) in self.block.iter_mut().zip(x.iter().zip(input.iter())) {
+            *out = mixed.wrapping_add(*original);
+        }
+
+        self.counter = self.counter.wrapping_add(1);
+        self.index = 0;
+    }
+
+    fn next_u32(&mut self) -> u32 {
+        if self.index == BLOCK_WORDS {
+            self.refill();
+        }
+        let value = self.block[self.index];
+        self.index += 1;
+        value
+    }
+}
+
+fn quarter_round(x: &mut [u32; BLOCK_WORDS], a: usize, b: usize, c: usize, d: usize) {
+    x[a] = x[a].wrapping_add(x[b]);
+    x[d] = (x[d] ^ x[a]).rotate_left(16);
+    x[c] = x[c].wrapping_add(x[d]);
+    x[b] = (x[b] ^ x[c]).rotate_left(12);
+    x[a] = x[a].wrapping_add(x[b]);
+    x[d] = (x[d] ^ x[a]).rotate_left(8);
+    x[c] = x[c].wrapping_add(x[d]);
+    x[b] = (x[b] ^ x[c]).rotate_left(7);
+}
+
+macro_rules! chacha_engine {
+    ($name:ident, $rounds:expr, $doc:expr) => {
+        #[doc = $doc]
+        #[derive(Clone)]
+        pub struct $name {
+            core: Core,
+        }
+
+        impl $name {
+            pub fn from_key(key: [u8; 32], stream: u64) -> $name {
+                $name {
+                    core: Core::new(key, stream, $rounds),
+                }
+            }
+
+            /// Keys the generator with 256 bits read from the operating system,
+            /// `Error::Unavailable` when it cannot provide them.
+            pub fn from_os() -> Result<$name, Error> {
+                let mut key = [0u8; 32];
+                entropy::fill(&mut key).map_err(|_| Error::Unavailable)?;
+                Ok($name::from_key(key, 0))
+            }
+        }
+
+        impl Engine for $name {
+            fn from_seed(seed: u128) -> $name {
+                let mut words = [0u64; 4];
+                expand_seed(seed, &mut words);
+
+                let mut key = [0u8; 32];
+                for (bytes, word) in key.chunks_mut(8).zip(words.iter()) {
+                    bytes.copy_from_slice(&word.to_le_bytes());
+                }
+                $name::from_key(key, 0)
+            }
+
+            // The full 256-bit key from the operating system, never a 128-bit seed
+            fn from_entropy() -> $name {
+                $name::from_os().expect("the operating system entropy source is unavailable")
+            }
+
+            fn next_u32(&mut self) -> u32 {
+                self.core.next_u32()
+            }
+
+            fn next_u64(&mut self) -> u64 {
+                let low = self.core.next_u32() as u64;
+                let high = self.core.next_u32() as u64;
+                (high << 32) | low
+            }
+        }
+
+        // Never print the key or the buffered output
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{} {{ .. }}", stringify!($name))
+            }
+        }
+    };
+}
+
+chacha_engine!(ChaCha8, 8, "ChaCha with 8 rounds, the fastest variant.");
+chacha_engine!(ChaCha12, 12, "ChaCha with 12 rounds.");
+chacha_engine!(
+    ChaCha20,
+    20,
+    "ChaCha with 20 rounds, the cryptographically secure default."
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chacha20_reference() {
+        // Test vectors 1 and 2 from https://tools.ietf.org/html/draft-nir-cfrg-chacha20-poly1305-04
+        let mut rng = ChaCha20::from_key([0; 32], 0);
+        let expected: [u32; 32] = [
+            0xade0b876, 0x903df1a0, 0xe56a5d40, 0x28bd8653, 0xb819d2bd, 0x1aed8da0, 0xccef36a8,
+            0xc70d778b, 0x7c5941da, 0x8d485751, 0x3fe02477, 0x374ad8b8, 0xf4b8436a, 0x1ca11815,
+            0x69b687c3, 0x8665eeb2, 0xbee7079f, 0x7a385155, 0x7c97ba98, 0x0d082d73, 0xa0290fcb,
+            0x6965e348, 0x3e53c612, 0xed7aee32, 0x7621b729, 0x434ee69c, 0xb03371d5, 0xd539d874,
+            0x281fed31, 0x45fb0a51, 0x1f0ae1ac, 0x6f4d794b,
+        ];
+        for value in expected.iter() {
+            assert_eq!(rng.next_u32(), *value);
+        }
+    }
+
+    #[test]
+    fn chacha20_stream_reference() {
+        // Test vector 5, a 96-bit nonce ending in 2 maps to the high byte of the stream id
+        let mut rng = ChaCha20::from_key([0; 32], 2 << 56);
+        let expected: [u32; 4] = [0x374dc6c2, 0x3736d58c, 0xb904e24a, 0xcd3f93ef];
+        for value in expected.iter() {
+            assert_eq!(rng.next_u32(), *value);
+        }
+    }
+
+    #[test]
+    fn round_counts_differ() {
+        let a = ChaCha8::from_seed(1).next_u64();
+        let b = ChaCha12::from_seed(1).next_u64();
+        let c = ChaCha20::from_seed(1).next_u64();
+        assert_ne!(a, b);
+        assert_ne!(b, c);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn from_os_is_unique() {
+        let mut a = ChaCha20::from_os().unwrap();
+        let mut b = ChaCha20::from_os().unwrap();
+        assert_ne!(a.next_u64(), b.next_u64());
+    }
+
+    #[test]
+    fn secure_random() {
+        let random = crate::Random::from_engine(ChaCha20::from_os().unwrap());
+        for _ in 0..100 {
+            let value: u32 = random.rand_range(10, 20);
+            assert!((10..20).contains(&value), "{} not in [10, 20)", value);
+        }
+    }
+
+    #[test]
+    fn debug_hides_state() {
+        let rng = ChaCha20::from_seed(1);
+        assert_eq!(format!("{:?}", rng), "ChaCha20 { .. }");
+
+        let rng = crate::Rng::<ChaCha20>::default();
+        assert_eq!(format!("{:?}", rng), "Rng { engine: ChaCha20 { .. } }");
+        assert_eq!(rng.get_seed(), 0);
+    }
+
+    #[test]
+    fn entropy_uses_the_whole_key() {
+        let mut a = crate::Rng::<ChaCha12>::from_entropy();
+        let mut b = crate::Rng::<ChaCha12>::from_entropy();
+        assert_ne!(a.next_u64(), b.next_u64());
+
+        // Not reproducible from anything the generator exposes
+        let mut c = crate::Rng::<ChaCha12>::with_seed(a.get_seed());
+        assert_ne!(a.next_u64(), c.next_u64());
+    }
+}
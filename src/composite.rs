@@ -0,0 +1,143 @@
+use crate::base::{Randomable, Rng};
+use crate::engine::Engine;
+use std::marker::PhantomData;
+
+macro_rules! tuple_randomable {
+    ($($name:ident),+) => {
+        impl<$($name: Randomable),+> Randomable for ($($name,)+) {
+            fn rand<E: Engine>(rng: &mut Rng<E>) -> ($($name,)+) {
+                // Tuple expressions are evaluated left to right
+                ($($name::rand(rng),)+)
+            }
+        }
+    };
+}
+
+tuple_randomable!(A);
+tuple_randomable!(A, B);
+tuple_randomable!(A, B, C);
+tuple_randomable!(A, B, C, D);
+tuple_randomable!(A, B, C, D, F);
+tuple_randomable!(A, B, C, D, F, G);
+tuple_randomable!(A, B, C, D, F, G, H);
+tuple_randomable!(A, B, C, D, F, G, H, I);
+tuple_randomable!(A, B, C, D, F, G, H, I, J);
+tuple_randomable!(A, B, C, D, F, G, H, I, J, K);
+tuple_randomable!(A, B, C, D, F, G, H, I, J, K, L);
+tuple_randomable!(A, B, C, D, F, G, H, I, J, K, L, M);
+
+impl<T: Randomable, const N: usize> Randomable for [T; N] {
+    fn rand<E: Engine>(rng: &mut Rng<E>) -> [T; N] {
+        std::array::from_fn(|_| T::rand(rng))
+    }
+}
+
+/// `None` half of the time, see `Rng::rand_option` for another probability.
+impl<T: Randomable> Randomable for Option<T> {
+    fn rand<E: Engine>(rng: &mut Rng<E>) -> Option<T> {
+        rng.rand_option(0.5)
+    }
+}
+
+/// `Ok` and `Err` are equally likely.
+impl<T: Randomable, F: Randomable> Randomable for Result<T, F> {
+    fn rand<E: Engine>(rng: &mut Rng<E>) -> Result<T, F> {
+        if bool::rand(rng) {
+            Ok(T::rand(rng))
+        } else {
+            Err(F::rand(rng))
+        }
+    }
+}
+
+/// Draws nothing, so derived types can carry marker fields.
+impl<T: ?Sized> Randomable for PhantomData<T> {
+    fn rand<E: Engine>(_rng: &mut Rng<E>) -> PhantomData<T> {
+        PhantomData
+    }
+}
+
+impl<E: Engine> Rng<E> {
+    /// `None` with probability `none_probability`, otherwise a random value.
+    pub fn rand_option<T: Randomable>(&mut self, none_probability: f64) -> Option<T> {
+        if self.gen_bool(none_probability) {
+            None
+        } else {
+            Some(T::rand(self))
+        }
+    }
+
+    pub fn rand_vec<T: Randomable>(&mut self, len: usize) -> Vec<T> {
+        (0..len).map(|_| T::rand(self)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tuples_draw_in_order() {
+        let mut a: Rng = Rng::with_seed(19);
+        let mut b: Rng = Rng::with_seed(19);
+
+        let (x, y, z) = <(u32, u64, u8)>::rand(&mut a);
+        assert_eq!(x, u32::rand(&mut b));
+        assert_eq!(y, u64::rand(&mut b));
+        assert_eq!(z, u8::rand(&mut b));
+
+        let wide = <(u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, bool)>::rand(&mut a);
+        assert_ne!((wide.0, wide.1, wide.2), (wide.3, wide.4, wide.5));
+    }
+
+    #[test]
+    fn arrays() {
+        let mut rng: Rng = Rng::with_seed(19);
+        let values = <[u64; 16]>::rand(&mut rng);
+        let mut unique = values.to_vec();
+        unique.sort_unstable();
+        unique.dedup();
+        assert_eq!(unique.len(), 16);
+
+        let empty = <[u8; 0]>::rand(&mut rng);
+        assert!(empty.is_empty());
+        let nested = <[[bool; 3]; 2]>::rand(&mut rng);
+        assert_eq!(nested.len(), 2);
+    }
+
+    #[test]
+    fn options() {
+        let mut rng: Rng = Rng::with_seed(19);
+        let none = (0..10_000)
+            .filter(|_| Option::<u8>::rand(&mut rng).is_none())
+            .count();
+        assert!((4_800..5_200).contains(&none), "{}", none);
+
+        let none = (0..10_000)
+            .filter(|_| rng.rand_option::<u8>(0.2).is_none())
+            .count();
+        assert!((1_850..2_150).contains(&none), "{}", none);
+
+        assert!((0..100).all(|_| rng.rand_option::<u8>(0.0).is_some()));
+        assert!((0..100).all(|_| rng.rand_option::<u8>(1.0).is_none()));
+    }
+
+    #[test]
+    fn results() {
+        let mut rng: Rng = Rng::with_seed(19);
+        let ok = (0..10_000)
+            .filter(|_| Result::<u8, i8>::rand(&mut rng).is_ok())
+            .count();
+        assert!((4_800..5_200).contains(&ok), "{}", ok);
+    }
+
+    #[test]
+    fn vecs() {
+        let mut rng: Rng = Rng::with_seed(19);
+        assert!(rng.rand_vec::<u8>(0).is_empty());
+
+        let values: Vec<(u16, bool)> = rng.rand_vec(100);
+        assert_eq!(values.len(), 100);
+        assert!(values.iter().any(|v| v.1) && values.iter().any(|v| !v.1));
+    }
+}
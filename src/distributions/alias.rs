@@ -0,0 +1,132 @@
+use super::weighted::{cumulative, Weight};
+use super::Distribution;
+use crate::base::Rng;
+use crate::engine::Engine;
+use crate::error::Error;
+
+/// Picks an index with probability proportional to its weight in constant time,
+/// with Vose's version of Walker's alias method. Building it takes O(n).
+#[derive(Debug, Clone, PartialEq)]
+pub struct AliasTable {
+    probability: Vec<f64>,
+    alias: Vec<usize>,
+}
+
+impl AliasTable {
+    /// Fails like `WeightedIndex::new`.
+    pub fn new<W: Weight, I: IntoIterator<Item = W>>(weights: I) -> Result<AliasTable, Error> {
+        let weights: Vec<W> = weights.into_iter().collect();
+        let total = cumulative(&weights)?[weights.len() - 1];
+
+        // Scaled so the average column holds exactly 1
+        let n = weights.len();
+        let total = total.to_f64();
+        let mut scaled: Vec<f64> = weights
+            .iter()
+            .map(|weight| weight.to_f64() * n as f64 / total)
+            .collect();
+        let mut small: Vec<usize> = (0..n).filter(|i| scaled[*i] < 1.0).collect();
+        let mut large: Vec<usize> = (0..n).filter(|i| scaled[*i] >= 1.0).collect();
+
+        // Each small column is topped up from a large one, which may turn small itself
+        let mut probability = vec![1.0; n];
+        let mut alias: Vec<usize> = (0..n).collect();
+        while let (Some(&s), Some(&l)) = (small.last(), large.last()) {
+            small.pop();
+            probability[s] = scaled[s];
+            alias[s] = l;
+            scaled[l] = (scaled[l] + scaled[s]) - 1.0;
+            if scaled[l] < 1.0 {
+                large.pop();
+                small.push(l);
+            }
+        }
+        // Whatever is left is 1 up to rounding and keeps its own index
+
+        Ok(AliasTable { probability, alias })
+    }
+
+    pub fn len(&self) -> usize {
+        self.probability.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.probability.is_empty()
+    }
+}
+
+impl Distribution<usize> for AliasTable {
+    fn sample<E: Engine>(&self, rng: &mut Rng<E>) -> usize {
+        let column = rng.randn_u64(self.probability.len() as u64) as usize;
+        if rng.next_f64() < self.probability[column] {
+            column
+        } else {
+            self.alias[column]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distributions::WeightedIndex;
+
+    #[test]
+    fn matches_weights() {
+        let weights = [70u64, 25, 0, 5, 1_000, 1];
+        let total: u64 = weights.iter().sum();
+        let table = AliasTable::new(weights.iter().copied()).unwrap();
+        assert_eq!(table.len(), 6);
+
+        let mut rng: Rng = Rng::with_seed(24);
+        let mut seen = [0usize; 6];
+        for _ in 0..500_000 {
+            seen[table.sample(&mut rng)] += 1;
+        }
+        for (count, weight) in seen.iter().zip(weights.iter()) {
+            let expected = *weight as f64 / total as f64;
+            let seen = *count as f64 / 500_000.0;
+            assert!((seen - expected).abs() < 0.003, "{} {}", seen, expected);
+        }
+        assert_eq!(seen[2], 0);
+    }
+
+    #[test]
+    fn agrees_with_weighted_index() {
+        let weights = [0.1, 0.2, 0.3, 0.15, 0.25f32];
+        let table = AliasTable::new(weights.iter().copied()).unwrap();
+        let index = WeightedIndex::new(weights.iter().copied()).unwrap();
+
+        let mut rng: Rng = Rng::with_seed(24);
+        let mut from_table = [0usize; 5];
+        let mut from_index = [0usize; 5];
+        for _ in 0..200_000 {
+            from_table[table.sample(&mut rng)] += 1;
+            from_index[index.sample(&mut rng)] += 1;
+        }
+        for (a, b) in from_table.iter().zip(from_index.iter()) {
+            assert!((*a as f64 - *b as f64).abs() < 1_500.0, "{} {}", a, b);
+        }
+    }
+
+    #[test]
+    fn uniform_and_single() {
+        let table = AliasTable::new(vec![3u8; 4]).unwrap();
+        assert!(table.probability.iter().all(|p| *p == 1.0));
+
+        let mut rng: Rng = Rng::with_seed(24);
+        let table = AliasTable::new(vec![0.0, 2.5]).unwrap();
+        assert!((0..100).all(|_| table.sample(&mut rng) == 1));
+    }
+
+    #[test]
+    fn invalid_weights() {
+        assert_eq!(AliasTable::new(Vec::<f64>::new()), Err(Error::EmptyRange));
+        assert_eq!(AliasTable::new(vec![0, 0]), Err(Error::EmptyRange));
+        assert_eq!(AliasTable::new(vec![1, -2]), Err(Error::InvalidWeight));
+        assert_eq!(
+            AliasTable::new(vec![f64::INFINITY]),
+            Err(Error::InvalidWeight)
+        );
+    }
+}
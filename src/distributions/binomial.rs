@@ -0,0 +1,267 @@
+use super::Distribution;
+use crate::base::Rng;
+use crate::engine::Engine;
+use crate::error::Error;
+
+/// The binomial distribution, the number of successes in `n` trials of probability `p`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Binomial {
+    n: u64,
+    p: f64,
+}
+
+impl Binomial {
+    pub fn new(n: u64, p: f64) -> Result<Binomial, Error> {
+        if !p.is_finite() {
+            return Err(Error::NonFiniteFloat);
+        }
+        if !(0.0..=1.0).contains(&p) {
+            return Err(Error::InvalidParameter);
+        }
+        Ok(Binomial { n, p })
+    }
+
+    pub fn n(&self) -> u64 {
+        self.n
+    }
+
+    pub fn p(&self) -> f64 {
+        self.p
+    }
+}
+
+impl Distribution<u64> for Binomial {
+    fn sample<E: Engine>(&self, rng: &mut Rng<E>) -> u64 {
+        if self.p == 0.0 || self.n == 0 {
+            return 0;
+        }
+        if self.p == 1.0 {
+            return self.n;
+        }
+
+        // Both algorithms want p <= 1/2, the count of failures is drawn otherwise
+        let r = self.p.min(1.0 - self.p);
+        let y = if self.n as f64 * r < 30.0 {
+            inversion(rng, self.n, r)
+        } else {
+            btpe(rng, self.n, r)
+        };
+        if self.p > 0.5 {
+            self.n - y
+        } else {
+            y
+        }
+    }
+}
+
+// Walks up the CDF, restarting in the rare case rounding runs it past any plausible value
+fn inversion<E: Engine>(rng: &mut Rng<E>, n: u64, p: f64) -> u64 {
+    let q = 1.0 - p;
+    let n_f = n as f64;
+    // q rounds to 1 for tiny p, ln(1 - p) has to come from p itself
+    let q_n = (n_f * (-p).ln_1p()).exp();
+    let odds = p / q;
+    let np = n_f * p;
+    let bound = n_f.min(np + 10.0 * (np * q + 1.0).sqrt()) as u64;
+
+    let mut x = 0;
+    let mut px = q_n;
+    let mut u = rng.next_f64();
+    while u > px {
+        x += 1;
+        if x > bound {
+            x = 0;
+            px = q_n;
+            u = rng.next_f64();
+        } else {
+            u -= px;
+            px *= (n_f - x as f64 + 1.0) / x as f64 * odds;
+        }
+    }
+    x
+}
+
+// Kachitvichyanukul and Schmeiser, "Binomial random variate generation", for n·p >= 30
+fn btpe<E: Engine>(rng: &mut Rng<E>, n: u64, p: f64) -> u64 {
+    let n_f = n as f64;
+    let q = 1.0 - p;
+    let npq = n_f * p * q;
+    let f_m = n_f * p + p;
+    let m = f_m.floor();
+
+    // The hat is a triangle, two parallelograms and two exponential tails
+    let p1 = (2.195 * npq.sqrt() - 4.6 * q).floor() + 0.5;
+    let x_m = m + 0.5;
+    let x_l = x_m - p1;
+    let x_r = x_m + p1;
+    let c = 0.134 + 20.5 / (15.3 + m);
+    let a = (f_m - x_l) / (f_m - x_l * p);
+    let lambda_l = a * (1.0 + a / 2.0);
+    let a = (x_r - f_m) / (x_r * q);
+    let lambda_r = a * (1.0 + a / 2.0);
+    let p2 = p1 * (1.0 + 2.0 * c);
+    let p3 = p2 + c / lambda_l;
+    let p4 = p3 + c / lambda_r;
+
+    loop {
+        let u = rng.next_f64() * p4;
+        let mut v = rng.next_f64();
+
+        let y = if u <= p1 {
+            // The triangle is always accepted
+            return (x_m - p1 * v + u).floor() as u64;
+        } else if u <= p2 {
+            let x = x_l + (u - p1) / c;
+            v = v * c + 1.0 - (m - x + 0.5).abs() / p1;
+            if v > 1.0 {
+                continue;
+            }
+            x.floor()
+        } else if u <= p3 {
+            let y = (x_l + v.ln() / lambda_l).floor();
+            if y < 0.0 {
+                continue;
+            }
+            v *= (u - p2) * lambda_l;
+            y
+        } else {
+            let y = (x_r - v.ln() / lambda_r).floor();
+            if y > n_f {
+                continue;
+            }
+            v *= (u - p3) * lambda_r;
+            y
+        };
+
+        let k = (y - m).abs();
+        if k <= 20.0 || k >= npq / 2.0 - 1.0 {
+            // Close to the mode, f(y)/f(m) is a short product
+            let s = p / q;
+            let a = s * (n_f + 1.0);
+            let mut f = 1.0;
+            if m < y {
+                let mut i = m + 1.0;
+                while i <= y {
+                    f *= a / i - s;
+                    i += 1.0;
+                }
+            } else if m > y {
+                let mut i = y + 1.0;
+                while i <= m {
+                    f /= a / i - s;
+                    i += 1.0;
+                }
+            }
+            if v <= f {
+                return y as u64;
+            }
+            continue;
+        }
+
+        // Squeeze on ln f(y)/f(m), then the exact bound with Stirling's corrections
+        let rho = (k / npq) * ((k * (k / 3.0 + 0.625) + 1.0 / 6.0) / npq + 0.5);
+        let t = -k * k / (2.0 * npq);
+        let ln_v = v.ln();
+        if ln_v < t - rho {
+            return y as u64;
+        }
+        if ln_v > t + rho {
+            continue;
+        }
+
+        let x1 = y + 1.0;
+        let f1 = m + 1.0;
+        let z = n_f + 1.0 - m;
+        let w = n_f - y + 1.0;
+        let bound = x_m * (f1 / x1).ln()
+            // z/w rounds to 1 for huge n, z - w = y - m is exact
+            + (n_f - m + 0.5) * ((y - m) / w).ln_1p()
+            + (y - m) * (w * p / (x1 * q)).ln()
+            + stirling(f1)
+            + stirling(z)
+            + stirling(x1)
+            + stirling(w);
+        if ln_v <= bound {
+            return y as u64;
+        }
+    }
+}
+
+// The correction term of Stirling's series, up to x^-9
+fn stirling(x: f64) -> f64 {
+    let x2 = x * x;
+    (13860.0 - (462.0 - (132.0 - (99.0 - 140.0 / x2) / x2) / x2) / x2) / x / 166_320.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distributions::math::ln_choose;
+    use crate::distributions::{counts, moments};
+
+    #[test]
+    fn binomial_moments() {
+        // Inversion, BTPE, and both of them with p above 1/2
+        for (n, p) in [
+            (20, 0.3),
+            (100, 0.05),
+            (1_000, 0.4),
+            (1_000, 0.9),
+            (1 << 40, 0.5),
+            (10_000_000_000_000_000_000, 1e-18),
+            (10_000_000_000_000_000_000, 1e-16),
+        ]
+        .iter()
+        {
+            let values = counts(&Binomial::new(*n, *p).unwrap(), 100_000);
+            assert!(values.iter().all(|v| *v <= *n as f64));
+
+            let (mean, variance) = moments(&values);
+            let (n, p) = (*n as f64, *p);
+            let expected_variance = n * p * (1.0 - p);
+            assert!(
+                (mean - n * p).abs() < 0.02 * expected_variance.sqrt(),
+                "{} {}",
+                n,
+                mean
+            );
+            assert!(
+                (variance - expected_variance).abs() < 0.03 * expected_variance,
+                "{} {}",
+                n,
+                variance
+            );
+        }
+    }
+
+    #[test]
+    fn btpe_matches_pmf() {
+        // Hits every region of the hat, including the Stirling bound far from the mode
+        let (n, p) = (500u64, 0.3f64);
+        let values = counts(&Binomial::new(n, p).unwrap(), 400_000);
+        for k in [125.0, 140.0, 150.0, 160.0, 175.0].iter() {
+            let pmf =
+                (ln_choose(n as f64, *k) + k * p.ln() + (n as f64 - k) * (1.0 - p).ln()).exp();
+            let seen = values.iter().filter(|v| *v == k).count() as f64 / 400_000.0;
+            assert!((seen - pmf).abs() < 0.1 * pmf, "{} {} {}", k, seen, pmf);
+        }
+    }
+
+    #[test]
+    fn edge_cases() {
+        let mut rng: Rng = Rng::with_seed(23);
+        let value: u64 = Binomial::new(10, 0.0).unwrap().sample(&mut rng);
+        assert_eq!(value, 0);
+        let value: u32 = Binomial::new(10, 1.0).unwrap().sample(&mut rng);
+        assert_eq!(value, 10);
+        let value: usize = Binomial::new(0, 0.5).unwrap().sample(&mut rng);
+        assert_eq!(value, 0);
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        assert_eq!(Binomial::new(10, 1.5), Err(Error::InvalidParameter));
+        assert_eq!(Binomial::new(10, -0.1), Err(Error::InvalidParameter));
+        assert_eq!(Binomial::new(10, f64::NAN), Err(Error::NonFiniteFloat));
+    }
+}
@@ -0,0 +1,62 @@
+use super::Distribution;
+use crate::base::Rng;
+use crate::engine::Engine;
+use crate::error::Error;
+use crate::interval::{Interval, Open01};
+use std::f64::consts::PI;
+
+/// The Cauchy distribution around `median`, half of the mass within `scale` of it.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Cauchy {
+    median: f64,
+    scale: f64,
+}
+
+impl Cauchy {
+    pub fn new(median: f64, scale: f64) -> Result<Cauchy, Error> {
+        if !median.is_finite() || !scale.is_finite() {
+            return Err(Error::NonFiniteFloat);
+        }
+        if scale <= 0.0 {
+            return Err(Error::InvalidParameter);
+        }
+        Ok(Cauchy { median, scale })
+    }
+}
+
+impl Distribution<f64> for Cauchy {
+    fn sample<E: Engine>(&self, rng: &mut Rng<E>) -> f64 {
+        // Inverse CDF, the open interval keeps tan away from ±π/2
+        let u = Open01::f64_from_bits(rng.next_u64());
+        self.median + self.scale * (PI * (u - 0.5)).tan()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quartiles() {
+        // No moments exist, the quartiles sit at median ± scale instead
+        let mut rng: Rng = Rng::with_seed(22);
+        let cauchy = Cauchy::new(3.0, 2.0).unwrap();
+        let values: Vec<f64> = cauchy.sample_iter(&mut rng).take(200_000).collect();
+        assert!(values.iter().all(|v| v.is_finite()));
+
+        let fraction =
+            |f: &dyn Fn(f64) -> bool| values.iter().filter(|v| f(**v)).count() as f64 / 200_000.0;
+        assert!((fraction(&|v| v < 3.0) - 0.5).abs() < 0.005);
+        assert!((fraction(&|v| v < 1.0) - 0.25).abs() < 0.005);
+        assert!((fraction(&|v| v < 5.0) - 0.75).abs() < 0.005);
+        // P(X > median + 10·scale) = 1/2 - atan(10)/π
+        let expected = 0.5 - 10f64.atan() / PI;
+        assert!((fraction(&|v| v > 23.0) - expected).abs() < 0.002);
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        assert_eq!(Cauchy::new(0.0, 0.0), Err(Error::InvalidParameter));
+        assert_eq!(Cauchy::new(f64::NAN, 1.0), Err(Error::NonFiniteFloat));
+    }
+}
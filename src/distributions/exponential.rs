@@ -0,0 +1,105 @@
+use super::ziggurat::{self, Table};
+use super::Distribution;
+use crate::base::Rng;
+use crate::engine::Engine;
+use crate::error::Error;
+use crate::interval::{Interval, OpenClosed01};
+
+const R: f64 = 7.697_117_470_131_05;
+const V: f64 = 0.003_949_659_822_581_557;
+
+lazy_static! {
+    static ref TABLE: Table = Table::new(R, V, pdf, pdf_inv);
+}
+
+fn pdf(x: f64) -> f64 {
+    (-x).exp()
+}
+
+fn pdf_inv(y: f64) -> f64 {
+    -y.ln()
+}
+
+/// A draw from Exp(1) through the Ziggurat.
+pub(crate) fn standard<E: Engine>(rng: &mut Rng<E>) -> f64 {
+    ziggurat::sample(rng, &TABLE, false, pdf, |rng, _| {
+        // The tail of an exponential is the same exponential shifted by R
+        R - OpenClosed01::f64_from_bits(rng.next_u64()).ln()
+    })
+}
+
+/// The exponential distribution Exp(lambda), the time between events of rate `lambda`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Exponential {
+    lambda: f64,
+}
+
+impl Exponential {
+    pub fn new(lambda: f64) -> Result<Exponential, Error> {
+        if !lambda.is_finite() {
+            return Err(Error::NonFiniteFloat);
+        }
+        if lambda <= 0.0 {
+            return Err(Error::InvalidParameter);
+        }
+        Ok(Exponential { lambda })
+    }
+
+    pub fn lambda(&self) -> f64 {
+        self.lambda
+    }
+}
+
+impl Distribution<f64> for Exponential {
+    fn sample<E: Engine>(&self, rng: &mut Rng<E>) -> f64 {
+        standard(rng) / self.lambda
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distributions::moments;
+
+    #[test]
+    fn table() {
+        // The base layer is (R + 1) wide since its area is R·e^-R plus the tail e^-R
+        assert!((TABLE.x[0] - (R + 1.0)).abs() < 1e-9);
+        assert!((TABLE.x[255] * (1.0 - TABLE.f[255]) - V).abs() < 1e-10);
+        assert_eq!(TABLE.x[256], 0.0);
+    }
+
+    #[test]
+    fn exponential_moments() {
+        let mut rng: Rng = Rng::with_seed(22);
+        let exp = Exponential::new(0.5).unwrap();
+        let values: Vec<f64> = exp.sample_iter(&mut rng).take(200_000).collect();
+        assert!(values.iter().all(|v| *v >= 0.0));
+
+        let (mean, variance) = moments(&values);
+        assert!((mean - 2.0).abs() < 0.02, "{}", mean);
+        assert!((variance - 4.0).abs() < 0.1, "{}", variance);
+
+        // P(X > t) = e^(-lambda·t)
+        let above = values.iter().filter(|v| **v > 4.0).count() as f64 / 200_000.0;
+        assert!((above - (-2.0f64).exp()).abs() < 0.003, "{}", above);
+    }
+
+    #[test]
+    fn tail_is_reached() {
+        let mut rng: Rng = Rng::with_seed(22);
+        // P(X > R) is about 4.5e-4
+        let tail = (0..1_000_000)
+            .map(|_| standard(&mut rng))
+            .filter(|v| *v > R)
+            .count();
+        assert!((300..600).contains(&tail), "{}", tail);
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        assert_eq!(Exponential::new(0.0), Err(Error::InvalidParameter));
+        assert_eq!(Exponential::new(-1.0), Err(Error::InvalidParameter));
+        assert_eq!(Exponential::new(f64::NAN), Err(Error::NonFiniteFloat));
+    }
+}
@@ -0,0 +1,221 @@
+use super::{normal, Distribution};
+use crate::base::Rng;
+use crate::engine::Engine;
+use crate::error::Error;
+use crate::interval::{Interval, Open01};
+
+// Finite and strictly positive, the domain of every parameter in this file
+fn check_positive(values: &[f64]) -> Result<(), Error> {
+    if values.iter().any(|v| !v.is_finite()) {
+        return Err(Error::NonFiniteFloat);
+    }
+    if values.iter().any(|v| *v <= 0.0) {
+        return Err(Error::InvalidParameter);
+    }
+    Ok(())
+}
+
+// Marsaglia and Tsang, "A Simple Method for Generating Gamma Variables", with scale 1
+pub(crate) fn standard<E: Engine>(rng: &mut Rng<E>, shape: f64) -> f64 {
+    if shape < 1.0 {
+        // Gamma(k) is Gamma(k + 1)·U^(1/k)
+        let u = Open01::f64_from_bits(rng.next_u64());
+        return standard(rng, shape + 1.0) * u.powf(1.0 / shape);
+    }
+
+    let d = shape - 1.0 / 3.0;
+    let c = 1.0 / (9.0 * d).sqrt();
+    loop {
+        let x = normal::standard(rng);
+        let v = 1.0 + c * x;
+        if v <= 0.0 {
+            continue;
+        }
+        let v = v * v * v;
+        let u = Open01::f64_from_bits(rng.next_u64());
+        let x2 = x * x;
+        if u < 1.0 - 0.0331 * x2 * x2 || u.ln() < 0.5 * x2 + d * (1.0 - v + v.ln()) {
+            return d * v;
+        }
+    }
+}
+
+/// The gamma distribution with `shape` k and `scale` θ, mean kθ.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Gamma {
+    shape: f64,
+    scale: f64,
+}
+
+impl Gamma {
+    pub fn new(shape: f64, scale: f64) -> Result<Gamma, Error> {
+        check_positive(&[shape, scale])?;
+        Ok(Gamma { shape, scale })
+    }
+
+    pub fn shape(&self) -> f64 {
+        self.shape
+    }
+
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+}
+
+impl Distribution<f64> for Gamma {
+    fn sample<E: Engine>(&self, rng: &mut Rng<E>) -> f64 {
+        standard(rng, self.shape) * self.scale
+    }
+}
+
+/// The chi-squared distribution with `k` degrees of freedom, a Gamma(k/2, 2).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ChiSquared {
+    k: f64,
+}
+
+impl ChiSquared {
+    pub fn new(k: f64) -> Result<ChiSquared, Error> {
+        check_positive(&[k])?;
+        Ok(ChiSquared { k })
+    }
+}
+
+impl Distribution<f64> for ChiSquared {
+    fn sample<E: Engine>(&self, rng: &mut Rng<E>) -> f64 {
+        standard(rng, self.k / 2.0) * 2.0
+    }
+}
+
+/// Student's t distribution with `nu` degrees of freedom.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct StudentT {
+    nu: f64,
+}
+
+impl StudentT {
+    pub fn new(nu: f64) -> Result<StudentT, Error> {
+        check_positive(&[nu])?;
+        Ok(StudentT { nu })
+    }
+}
+
+impl Distribution<f64> for StudentT {
+    fn sample<E: Engine>(&self, rng: &mut Rng<E>) -> f64 {
+        // Z / sqrt(V/nu) with V a chi-squared of nu degrees
+        let z = normal::standard(rng);
+        let v = standard(rng, self.nu / 2.0) * 2.0;
+        z / (v / self.nu).sqrt()
+    }
+}
+
+/// The beta distribution on [0, 1] with shapes `alpha` and `beta`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Beta {
+    alpha: f64,
+    beta: f64,
+}
+
+impl Beta {
+    pub fn new(alpha: f64, beta: f64) -> Result<Beta, Error> {
+        check_positive(&[alpha, beta])?;
+        Ok(Beta { alpha, beta })
+    }
+}
+
+impl Distribution<f64> for Beta {
+    fn sample<E: Engine>(&self, rng: &mut Rng<E>) -> f64 {
+        // X / (X + Y), redrawn in the rare case both underflow with tiny shapes
+        loop {
+            let x = standard(rng, self.alpha);
+            let y = standard(rng, self.beta);
+            if x + y > 0.0 {
+                return x / (x + y);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distributions::moments;
+
+    fn draw<D: Distribution<f64>>(distribution: D, n: usize) -> Vec<f64> {
+        let mut rng: Rng = Rng::with_seed(22);
+        distribution.sample_iter(&mut rng).take(n).collect()
+    }
+
+    #[test]
+    fn gamma_moments() {
+        // Both branches, shape above and below 1
+        for (shape, scale) in [(3.0, 2.0), (0.5, 4.0), (1.0, 1.0)].iter() {
+            let values = draw(Gamma::new(*shape, *scale).unwrap(), 200_000);
+            assert!(values.iter().all(|v| *v >= 0.0));
+
+            let (mean, variance) = moments(&values);
+            let (expected_mean, expected_variance) = (shape * scale, shape * scale * scale);
+            assert!(
+                (mean - expected_mean).abs() < 0.01 * expected_mean,
+                "{} {}",
+                shape,
+                mean
+            );
+            assert!(
+                (variance - expected_variance).abs() < 0.03 * expected_variance,
+                "{} {}",
+                shape,
+                variance
+            );
+        }
+    }
+
+    #[test]
+    fn chi_squared_moments() {
+        let values = draw(ChiSquared::new(4.0).unwrap(), 200_000);
+        let (mean, variance) = moments(&values);
+        assert!((mean - 4.0).abs() < 0.04, "{}", mean);
+        assert!((variance - 8.0).abs() < 0.2, "{}", variance);
+    }
+
+    #[test]
+    fn student_t_moments() {
+        // The variance is nu / (nu - 2)
+        let values = draw(StudentT::new(10.0).unwrap(), 200_000);
+        let (mean, variance) = moments(&values);
+        assert!(mean.abs() < 0.01, "{}", mean);
+        assert!((variance - 1.25).abs() < 0.04, "{}", variance);
+
+        let negative = values.iter().filter(|v| **v < 0.0).count() as f64 / 200_000.0;
+        assert!((negative - 0.5).abs() < 0.005, "{}", negative);
+    }
+
+    #[test]
+    fn beta_moments() {
+        for (a, b) in [(2.0, 5.0), (0.5, 0.5), (0.01, 0.01)].iter() {
+            let values = draw(Beta::new(*a, *b).unwrap(), 100_000);
+            assert!(values.iter().all(|v| (0.0..=1.0).contains(v)));
+
+            let (mean, variance) = moments(&values);
+            let sum = a + b;
+            let expected_variance = a * b / (sum * sum * (sum + 1.0));
+            assert!((mean - a / sum).abs() < 0.005, "{} {}", a, mean);
+            assert!(
+                (variance - expected_variance).abs() < 0.03 * expected_variance,
+                "{} {}",
+                a,
+                variance
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        assert_eq!(Gamma::new(0.0, 1.0), Err(Error::InvalidParameter));
+        assert_eq!(Gamma::new(1.0, -2.0), Err(Error::InvalidParameter));
+        assert_eq!(Gamma::new(f64::INFINITY, 1.0), Err(Error::NonFiniteFloat));
+        assert_eq!(ChiSquared::new(-1.0), Err(Error::InvalidParameter));
+        assert_eq!(StudentT::new(f64::NAN), Err(Error::NonFiniteFloat));
+        assert_eq!(Beta::new(1.0, 0.0), Err(Error::InvalidParameter));
+    }
+}
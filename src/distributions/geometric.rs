@@ -0,0 +1,151 @@
+use super::{gamma, poisson, Distribution};
+use crate::base::Rng;
+use crate::engine::Engine;
+use crate::error::Error;
+use crate::interval::{Interval, OpenClosed01};
+
+// A success probability in (0, 1]
+fn check_probability(p: f64) -> Result<(), Error> {
+    if !p.is_finite() {
+        return Err(Error::NonFiniteFloat);
+    }
+    if p <= 0.0 || p > 1.0 {
+        return Err(Error::InvalidParameter);
+    }
+    Ok(())
+}
+
+/// The geometric distribution, the number of failures before the first success of probability `p`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Geometric {
+    p: f64,
+}
+
+impl Geometric {
+    pub fn new(p: f64) -> Result<Geometric, Error> {
+        check_probability(p)?;
+        Ok(Geometric { p })
+    }
+
+    pub fn p(&self) -> f64 {
+        self.p
+    }
+}
+
+impl Distribution<u64> for Geometric {
+    fn sample<E: Engine>(&self, rng: &mut Rng<E>) -> u64 {
+        if self.p == 1.0 {
+            return 0;
+        }
+        // Inverse CDF, the cast saturates for the huge counts of a tiny p
+        let u = OpenClosed01::f64_from_bits(rng.next_u64());
+        (u.ln() / (-self.p).ln_1p()).floor() as u64
+    }
+}
+
+/// The negative binomial distribution, the number of failures before `r` successes of probability `p`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct NegativeBinomial {
+    r: f64,
+    p: f64,
+}
+
+impl NegativeBinomial {
+    /// `r` may be fractional, giving the Pólya distribution.
+    pub fn new(r: f64, p: f64) -> Result<NegativeBinomial, Error> {
+        check_probability(p)?;
+        if !r.is_finite() {
+            return Err(Error::NonFiniteFloat);
+        }
+        if r <= 0.0 {
+            return Err(Error::InvalidParameter);
+        }
+        Ok(NegativeBinomial { r, p })
+    }
+}
+
+impl Distribution<u64> for NegativeBinomial {
+    fn sample<E: Engine>(&self, rng: &mut Rng<E>) -> u64 {
+        if self.p == 1.0 {
+            return 0;
+        }
+        // A Poisson whose rate is Gamma(r, (1 - p)/p)
+        let lambda = gamma::standard(rng, self.r) * (1.0 - self.p) / self.p;
+        poisson::sample(rng, lambda)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distributions::{counts, moments};
+
+    #[test]
+    fn geometric_moments() {
+        // Mean (1 - p)/p, variance (1 - p)/p²
+        for p in [0.5, 0.1, 0.9, 0.001].iter() {
+            let values = counts(&Geometric::new(*p).unwrap(), 200_000);
+            let (mean, variance) = moments(&values);
+            let expected_mean = (1.0 - p) / p;
+            let expected_variance = expected_mean / p;
+            assert!(
+                (mean - expected_mean).abs() < 0.02 * expected_variance.sqrt(),
+                "{} {}",
+                p,
+                mean
+            );
+            assert!(
+                (variance - expected_variance).abs() < 0.04 * expected_variance,
+                "{} {}",
+                p,
+                variance
+            );
+        }
+
+        let zero = counts(&Geometric::new(0.3).unwrap(), 100_000)
+            .iter()
+            .filter(|v| **v == 0.0)
+            .count();
+        assert!((29_500..30_500).contains(&zero), "{}", zero);
+        assert!(counts(&Geometric::new(1.0).unwrap(), 10)
+            .iter()
+            .all(|v| *v == 0.0));
+    }
+
+    #[test]
+    fn negative_binomial_moments() {
+        // Mean r(1 - p)/p, variance r(1 - p)/p²
+        for (r, p) in [(5.0, 0.5), (2.5, 0.2), (100.0, 0.9)].iter() {
+            let values = counts(&NegativeBinomial::new(*r, *p).unwrap(), 200_000);
+            let (mean, variance) = moments(&values);
+            let expected_mean = r * (1.0 - p) / p;
+            let expected_variance = expected_mean / p;
+            assert!(
+                (mean - expected_mean).abs() < 0.02 * expected_variance.sqrt(),
+                "{} {}",
+                r,
+                mean
+            );
+            assert!(
+                (variance - expected_variance).abs() < 0.04 * expected_variance,
+                "{} {}",
+                r,
+                variance
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        assert_eq!(Geometric::new(0.0), Err(Error::InvalidParameter));
+        assert_eq!(Geometric::new(1.1), Err(Error::InvalidParameter));
+        assert_eq!(
+            NegativeBinomial::new(0.0, 0.5),
+            Err(Error::InvalidParameter)
+        );
+        assert_eq!(
+            NegativeBinomial::new(1.0, f64::NAN),
+            Err(Error::NonFiniteFloat)
+        );
+    }
+}
@@ -0,0 +1,288 @@
+use super::math::ln_binomial_pmf;
+use super::Distribution;
+use crate::base::Rng;
+use crate::engine::Engine;
+use crate::error::Error;
+use crate::interval::{Interval, OpenClosed01};
+
+// Stadlober's ratio-of-uniforms hat, 2·sqrt(2/e) and 3 - 2·sqrt(3/e)
+const D1: f64 = 1.715_527_769_921_413_5;
+const D2: f64 = 0.898_916_162_058_898_8;
+
+// Below this standard deviation searching from the mode is cheaper than rejection
+const SEARCH_LIMIT: f64 = 10.0;
+
+/// The hypergeometric distribution, the number of successes in `draws` taken
+/// without replacement from `total` items of which `successes` are successes.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Hypergeometric {
+    total: u64,
+    successes: u64,
+    draws: u64,
+    min: u64,
+    max: u64,
+    mode: u64,
+    ln_mode_probability: f64,
+    mode_probability: f64,
+    // The ratio-of-uniforms hat, a width of 0 selects the search
+    center: f64,
+    width: f64,
+}
+
+impl Hypergeometric {
+    pub fn new(total: u64, successes: u64, draws: u64) -> Result<Hypergeometric, Error> {
+        if successes > total || draws > total {
+            return Err(Error::InvalidParameter);
+        }
+
+        // draws + successes - total, without overflowing the sum
+        let min = draws.saturating_sub(total - successes);
+        let max = successes.min(draws);
+        let (n, k, d) = (total as f64, successes as f64, draws as f64);
+        // (d + 1)(k + 1)/(n + 2) in integers, f64 rounds it off the support near u64::MAX.
+        // The product only saturates at d = k = u64::MAX, where the quotient is still exact.
+        let mode = (u128::from(draws) + 1).saturating_mul(u128::from(successes) + 1)
+            / (u128::from(total) + 2);
+        let mode = (mode as u64).clamp(min, max);
+
+        let mut hypergeometric = Hypergeometric {
+            total,
+            successes,
+            draws,
+            min,
+            max,
+            mode,
+            ln_mode_probability: 0.0,
+            mode_probability: 1.0,
+            center: 0.0,
+            width: 0.0,
+        };
+        if min == max {
+            return Ok(hypergeometric);
+        }
+
+        hypergeometric.ln_mode_probability = hypergeometric.ln_probability(mode);
+        hypergeometric.mode_probability = hypergeometric.ln_mode_probability.exp();
+        let mean = d * k / n;
+        let variance = mean * (n - k) / n * (n - d) / (n - 1.0);
+        if variance >= SEARCH_LIMIT * SEARCH_LIMIT {
+            hypergeometric.center = mean + 0.5;
+            hypergeometric.width = D1 * (variance + 0.5).sqrt() + D2;
+        }
+        Ok(hypergeometric)
+    }
+
+    // ln f(x) as the binomial pmfs B(x; K, p)·B(d - x; N - K, p) / B(d; N, p), exact for
+    // any p. Differences of ln_gamma lose every digit once the total reaches ~1e15.
+    fn ln_probability(&self, x: u64) -> f64 {
+        let n = self.total as f64;
+        let p = self.draws as f64 / n;
+        let q = (self.total - self.draws) as f64 / n;
+        ln_binomial_pmf(x as f64, self.successes as f64, p, q)
+            + ln_binomial_pmf(
+                (self.draws - x) as f64,
+                (self.total - self.successes) as f64,
+                p,
+                q,
+            )
+            - ln_binomial_pmf(self.draws as f64, n, p, q)
+    }
+
+    // f(x + 1) / f(x)
+    fn ratio(&self, x: u64) -> f64 {
+        let (n, k, d, x) = (
+            self.total as f64,
+            self.successes as f64,
+            self.draws as f64,
+            x as f64,
+        );
+        (k - x) * (d - x) / ((x + 1.0) * (n - k - d + x + 1.0))
+    }
+
+    // Inversion searching outwards from the mode, about a standard deviation of steps
+    fn search<E: Engine>(&self, rng: &mut Rng<E>) -> u64 {
+        let mut u = rng.next_f64();
+        if u < self.mode_probability {
+            return self.mode;
+        }
+        u -= self.mode_probability;
+
+        let (mut low, mut high) = (self.mode, self.mode);
+        let (mut low_probability, mut high_probability) =
+            (self.mode_probability, self.mode_probability);
+        while low > self.min || high < self.max {
+            if high < self.max {
+                high_probability *= self.ratio(high);
+                high += 1;
+                if u < high_probability {
+                    return high;
+                }
+                u -= high_probability;
+            }
+            if low > self.min {
+                low -= 1;
+                low_probability /= self.ratio(low);
+                if u < low_probability {
+                    return low;
+                }
+                u -= low_probability;
+            }
+        }
+        // Only rounding in the probabilities leaves u here
+        self.mode
+    }
+}
+
+impl Distribution<u64> for Hypergeometric {
+    // Stadlober, "The ratio of uniforms approach for generating discrete random
+    // variates", constant expected time however wide the distribution is
+    fn sample<E: Engine>(&self, rng: &mut Rng<E>) -> u64 {
+        if self.width == 0.0 {
+            return self.search(rng);
+        }
+        loop {
+            let u = OpenClosed01::f64_from_bits(rng.next_u64());
+            let v = rng.next_f64() - 0.5;
+            let w = self.center + self.width * v / u;
+            if w < self.min as f64 || w >= self.max as f64 + 1.0 {
+                continue;
+            }
+            let x = w as u64;
+
+            // Accept when u² <= f(x)/f(mode), the squeezes skip most logarithms
+            let t = self.ln_probability(x) - self.ln_mode_probability;
+            if u * (4.0 - u) - 3.0 <= t {
+                return x;
+            }
+            if u * (u - t) >= 1.0 {
+                continue;
+            }
+            if 2.0 * u.ln() <= t {
+                return x;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distributions::math::ln_choose;
+    use crate::distributions::{counts, moments};
+
+    #[test]
+    fn hypergeometric_moments() {
+        for (total, successes, draws) in [
+            (50, 5, 10),
+            (1_000, 400, 300),
+            (10, 10, 4),
+            (100, 70, 90),
+            (1_000_000, 10, 500_000),
+            (100_000, 40_000, 30_000),
+            (u64::MAX, u64::MAX / 3, 1 << 40),
+            (u64::MAX, u64::MAX - 1, u64::MAX - 1),
+        ]
+        .iter()
+        {
+            let hypergeometric = Hypergeometric::new(*total, *successes, *draws).unwrap();
+            let values = counts(&hypergeometric, 100_000);
+            assert!(values
+                .iter()
+                .all(|v| *v >= hypergeometric.min as f64 && *v <= hypergeometric.max as f64));
+
+            // Mean dK/N, variance dK/N·(N - K)/N·(N - d)/(N - 1)
+            let (mean, variance) = moments(&values);
+            let (n, k, d) = (*total as f64, *successes as f64, *draws as f64);
+            let expected_mean = d * k / n;
+            let expected_variance = expected_mean * (n - k) / n * (n - d) / (n - 1.0);
+            assert!(
+                (mean - expected_mean).abs() < 0.02 * expected_variance.sqrt().max(0.01),
+                "{} {}",
+                total,
+                mean
+            );
+            assert!(
+                (variance - expected_variance).abs() <= 0.03 * expected_variance,
+                "{} {}",
+                total,
+                variance
+            );
+        }
+    }
+
+    #[test]
+    fn matches_pmf() {
+        // Drawing 5 cards, the number of hearts
+        let hypergeometric = Hypergeometric::new(52, 13, 5).unwrap();
+        let values = counts(&hypergeometric, 200_000);
+        for k in 0..=5 {
+            let k = k as f64;
+            let pmf = (ln_choose(13.0, k) + ln_choose(39.0, 5.0 - k) - ln_choose(52.0, 5.0)).exp();
+            let seen = values.iter().filter(|v| **v == k).count() as f64 / 200_000.0;
+            assert!((seen - pmf).abs() < 0.1 * pmf, "{} {} {}", k, seen, pmf);
+        }
+    }
+
+    #[test]
+    fn ratio_of_uniforms_matches_pmf() {
+        let hypergeometric = Hypergeometric::new(10_000, 4_000, 3_000).unwrap();
+        assert!(hypergeometric.width > 0.0);
+        let values = counts(&hypergeometric, 200_000);
+        for k in [1_160.0, 1_180.0, 1_200.0, 1_220.0, 1_240.0].iter() {
+            let pmf = (ln_choose(4_000.0, *k) + ln_choose(6_000.0, 3_000.0 - k)
+                - ln_choose(10_000.0, 3_000.0))
+            .exp();
+            let seen = values.iter().filter(|v| *v == k).count() as f64 / 200_000.0;
+            assert!(
+                (seen - pmf).abs() < 4.0 * (pmf / 200_000.0).sqrt(),
+                "{} {} {}",
+                k,
+                seen,
+                pmf
+            );
+        }
+    }
+
+    #[test]
+    fn huge_total() {
+        // draws + successes overflows u64 here. The single item left undrawn is
+        // a success with probability 1 - 1/u64::MAX, so the draws miss one of them.
+        let nearly_all = Hypergeometric::new(u64::MAX, u64::MAX - 1, u64::MAX - 1).unwrap();
+        assert_eq!(
+            (nearly_all.min, nearly_all.max),
+            (u64::MAX - 2, u64::MAX - 1)
+        );
+        assert_eq!(nearly_all.mode, u64::MAX - 2);
+        let mut rng: Rng = Rng::with_seed(23);
+        for _ in 0..1_000 {
+            let value: u64 = nearly_all.sample(&mut rng);
+            assert_eq!(value, u64::MAX - 2);
+        }
+
+        // Half of a quadrillion items are successes, 1000 draws are Binomial(1000, 0.5)
+        let hypergeometric =
+            Hypergeometric::new(1_000_000_000_000_000, 500_000_000_000_000, 1_000).unwrap();
+        let binomial = (ln_choose(1_000.0, 500.0) - 1_000.0 * 2f64.ln()).exp();
+        assert_eq!(hypergeometric.mode, 500);
+        assert!(
+            (hypergeometric.mode_probability - binomial).abs() < 1e-9,
+            "{} {}",
+            hypergeometric.mode_probability,
+            binomial
+        );
+
+        let values = counts(&hypergeometric, 200_000);
+        let (mean, variance) = moments(&values);
+        assert!((mean - 500.0).abs() < 0.2, "{}", mean);
+        assert!((variance - 250.0).abs() < 7.5, "{}", variance);
+        let seen = values.iter().filter(|v| **v == 500.0).count() as f64 / 200_000.0;
+        assert!((seen - binomial).abs() < 0.002, "{} {}", seen, binomial);
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        assert_eq!(Hypergeometric::new(10, 11, 2), Err(Error::InvalidParameter));
+        assert_eq!(Hypergeometric::new(10, 2, 11), Err(Error::InvalidParameter));
+        assert!(Hypergeometric::new(0, 0, 0).is_ok());
+    }
+}
@@ -0,0 +1,142 @@
+use std::f64::consts::PI;
+
+// Lanczos approximation with g = 7, n = 9
+const G: f64 = 7.0;
+const LANCZOS: [f64; 9] = [
+    0.999_999_999_999_809_9,
+    676.520_368_121_885_1,
+    -1_259.139_216_722_402_8,
+    771.323_428_777_653_1,
+    -176.615_029_162_140_6,
+    12.507_343_278_686_905,
+    -0.138_571_095_265_720_12,
+    9.984_369_578_019_572e-6,
+    1.505_632_735_149_311_6e-7,
+];
+
+/// ln Γ(x), accurate to about 1e-13 relative for positive `x`.
+pub(crate) fn ln_gamma(x: f64) -> f64 {
+    if x < 0.5 {
+        // Reflection, Γ(x)Γ(1 - x) = π / sin(πx)
+        return (PI / (PI * x).sin().abs()).ln() - ln_gamma(1.0 - x);
+    }
+
+    let x = x - 1.0;
+    let mut a = LANCZOS[0];
+    for (i, c) in LANCZOS.iter().enumerate().skip(1) {
+        a += c / (x + i as f64);
+    }
+    let t = x + G + 0.5;
+    0.5 * (2.0 * PI).ln() + (x + 0.5) * t.ln() - t + a.ln()
+}
+
+/// ln of the binomial coefficient `n` choose `k`. Differences of these cancel
+/// for huge `n`, where `ln_binomial_pmf` is the accurate form.
+#[cfg(test)]
+pub(crate) fn ln_choose(n: f64, k: f64) -> f64 {
+    ln_gamma(n + 1.0) - ln_gamma(k + 1.0) - ln_gamma(n - k + 1.0)
+}
+
+/// ln of the binomial pmf at `x` for `n` trials. `q = 1 - p` is passed too so
+/// neither loses precision near 0. Loader's saddle point form, "Fast and accurate
+/// computation of binomial probabilities", stays accurate for `n` in the trillions.
+pub(crate) fn ln_binomial_pmf(x: f64, n: f64, p: f64, q: f64) -> f64 {
+    if x == 0.0 {
+        let ln_q = if p < 0.5 { (-p).ln_1p() } else { q.ln() };
+        return n * ln_q;
+    }
+    if x == n {
+        let ln_p = if q < 0.5 { (-q).ln_1p() } else { p.ln() };
+        return n * ln_p;
+    }
+    let y = n - x;
+    stirling_error(n)
+        - stirling_error(x)
+        - stirling_error(y)
+        - deviance(x, n * p)
+        - deviance(y, n * q)
+        - 0.5 * (2.0 * PI * x * (y / n)).ln()
+}
+
+// ln Γ(n + 1) - ((n + 0.5)·ln n - n + ln sqrt(2π)), what Stirling's formula misses
+fn stirling_error(n: f64) -> f64 {
+    if n <= 15.0 {
+        return ln_gamma(n + 1.0) - (n + 0.5) * n.ln() + n - 0.5 * (2.0 * PI).ln();
+    }
+    let nn = n * n;
+    (1.0 / 12.0
+        - (1.0 / 360.0 - (1.0 / 1260.0 - (1.0 / 1680.0 - 1.0 / (1188.0 * nn)) / nn) / nn) / nn)
+        / n
+}
+
+// x·ln(x/np) + np - x, summed as a series when x is close to np where the terms cancel
+fn deviance(x: f64, np: f64) -> f64 {
+    if (x - np).abs() >= 0.1 * (x + np) {
+        return x * (x / np).ln() + np - x;
+    }
+    let v = (x - np) / (x + np);
+    let mut sum = (x - np) * v;
+    let mut term = 2.0 * x * v;
+    let mut j = 1.0;
+    loop {
+        term *= v * v;
+        let next = sum + term / (2.0 * j + 1.0);
+        if next == sum {
+            return sum;
+        }
+        sum = next;
+        j += 1.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ln_gamma_values() {
+        let factorial: f64 = (1..=20).map(|i| i as f64).product();
+        assert!((ln_gamma(21.0) - factorial.ln()).abs() < 1e-12);
+        assert!(ln_gamma(1.0).abs() < 1e-14);
+        assert!(ln_gamma(2.0).abs() < 1e-14);
+        assert!((ln_gamma(0.5) - PI.sqrt().ln()).abs() < 1e-14);
+        // Γ(0.1) = 9.513507698668732
+        assert!((ln_gamma(0.1) - 9.513_507_698_668_732f64.ln()).abs() < 1e-13);
+        // Stirling's series holds for large x
+        let x = 1e10f64;
+        let stirling = (x - 0.5) * x.ln() - x + 0.5 * (2.0 * PI).ln() + 1.0 / (12.0 * x);
+        assert!((ln_gamma(x) - stirling).abs() / stirling < 1e-14);
+    }
+
+    #[test]
+    fn ln_choose_values() {
+        assert!((ln_choose(10.0, 3.0) - 120f64.ln()).abs() < 1e-12);
+        assert!(ln_choose(7.0, 0.0).abs() < 1e-12);
+        assert!((ln_choose(52.0, 5.0) - 2_598_960f64.ln()).abs() < 1e-11);
+    }
+
+    #[test]
+    fn ln_binomial_pmf_values() {
+        for (n, p) in [(20.0f64, 0.3f64), (1.0, 0.5), (500.0, 0.999), (60.0, 1e-3)].iter() {
+            let q = 1.0 - p;
+            for x in [0.0, 1.0, (n / 2.0).floor(), n - 1.0, *n].iter() {
+                let direct = ln_choose(*n, *x) + x * p.ln() + (n - x) * q.ln();
+                let loader = ln_binomial_pmf(*x, *n, *p, q);
+                assert!(
+                    (loader - direct).abs() < 1e-9 * direct.abs().max(1.0),
+                    "{} {} {}",
+                    n,
+                    p,
+                    x
+                );
+            }
+        }
+
+        // A trillion trials at p = 1e-12 are Poisson(500), ln P(500) = -500 + 500·ln 500 - ln 500!
+        let n = 5e14;
+        let p = 1e-12;
+        let poisson = -500.0 + 500.0 * 500f64.ln() - ln_gamma(501.0);
+        let loader = ln_binomial_pmf(500.0, n, p, 1.0 - p);
+        assert!((loader - poisson).abs() < 1e-9, "{} {}", loader, poisson);
+    }
+}
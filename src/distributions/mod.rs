@@ -0,0 +1,184 @@
+use crate::base::Rng;
+use crate::engine::Engine;
+use std::convert::TryFrom;
+use std::marker::PhantomData;
+
+mod alias;
+mod binomial;
+mod cauchy;
+mod exponential;
+mod gamma;
+mod geometric;
+mod hypergeometric;
+mod math;
+mod normal;
+mod pareto;
+mod poisson;
+mod triangular;
+mod weibull;
+mod weighted;
+mod ziggurat;
+mod zipf;
+
+pub use self::alias::AliasTable;
+pub use self::binomial::Binomial;
+pub use self::cauchy::Cauchy;
+pub use self::exponential::Exponential;
+pub use self::gamma::{Beta, ChiSquared, Gamma, StudentT};
+pub use self::geometric::{Geometric, NegativeBinomial};
+pub use self::hypergeometric::Hypergeometric;
+pub use self::normal::{LogNormal, Normal};
+pub use self::pareto::Pareto;
+pub use self::poisson::Poisson;
+pub use self::triangular::Triangular;
+pub use self::weibull::Weibull;
+pub use self::weighted::{Weight, WeightedIndex};
+pub use self::zipf::{PowerLaw, Zipf};
+
+/// A probability distribution values of type `T` are drawn from.
+pub trait Distribution<T> {
+    fn sample<E: Engine>(&self, rng: &mut Rng<E>) -> T;
+
+    /// An endless iterator of samples, e.g. `normal.sample_iter(&mut rng).take(100)`.
+    fn sample_iter<E: Engine>(self, rng: &mut Rng<E>) -> SampleIter<'_, Self, E, T>
+    where
+        Self: Sized,
+    {
+        SampleIter {
+            distribution: self,
+            rng,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// The iterator returned by `Distribution::sample_iter`.
+#[derive(Debug)]
+pub struct SampleIter<'a, D, E, T> {
+    distribution: D,
+    rng: &'a mut Rng<E>,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<D: Distribution<T>, E: Engine, T> Iterator for SampleIter<'_, D, E, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        Some(self.distribution.sample(self.rng))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+// Continuous distributions compute in f64, f32 samples are rounded from it
+macro_rules! f32_from_f64 {
+    ($($ty:ty),+) => {
+        $(
+            impl Distribution<f32> for $ty {
+                fn sample<E: Engine>(&self, rng: &mut Rng<E>) -> f32 {
+                    Distribution::<f64>::sample(self, rng) as f32
+                }
+            }
+        )+
+    };
+}
+
+f32_from_f64!(
+    Beta,
+    Cauchy,
+    ChiSquared,
+    Exponential,
+    Gamma,
+    LogNormal,
+    Normal,
+    Pareto,
+    StudentT,
+    Triangular,
+    Weibull
+);
+
+// Discrete distributions count in u64, narrower samples saturate at their MAX
+macro_rules! narrow_from_u64 {
+    ($($ty:ty),+) => {
+        $(
+            impl Distribution<u32> for $ty {
+                fn sample<E: Engine>(&self, rng: &mut Rng<E>) -> u32 {
+                    let value: u64 = self.sample(rng);
+                    u32::try_from(value).unwrap_or(u32::MAX)
+                }
+            }
+
+            impl Distribution<usize> for $ty {
+                fn sample<E: Engine>(&self, rng: &mut Rng<E>) -> usize {
+                    let value: u64 = self.sample(rng);
+                    usize::try_from(value).unwrap_or(usize::MAX)
+                }
+            }
+        )+
+    };
+}
+
+narrow_from_u64!(
+    Binomial,
+    Geometric,
+    Hypergeometric,
+    NegativeBinomial,
+    Poisson,
+    PowerLaw,
+    Zipf
+);
+
+impl<E: Engine> Rng<E> {
+    /// A value drawn from `distribution`, e.g. `rng.sample::<f64, _>(&Normal::new(0.0, 1.0)?)`.
+    pub fn sample<T, D: Distribution<T>>(&mut self, distribution: &D) -> T {
+        distribution.sample(self)
+    }
+
+    pub fn sample_iter<T, D: Distribution<T>>(
+        &mut self,
+        distribution: D,
+    ) -> SampleIter<'_, D, E, T> {
+        distribution.sample_iter(self)
+    }
+}
+
+// Mean and population variance, for the moment tests of each distribution
+#[cfg(test)]
+fn moments(values: &[f64]) -> (f64, f64) {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values.iter().map(|v| (v - mean) * (v - mean)).sum::<f64>() / n;
+    (mean, variance)
+}
+
+// Draws from a discrete distribution with a fixed seed, as f64 for `moments`
+#[cfg(test)]
+fn counts<D: Distribution<u64>>(distribution: &D, n: usize) -> Vec<f64> {
+    let mut rng: Rng = Rng::with_seed(23);
+    (0..n)
+        .map(|_| distribution.sample(&mut rng) as f64)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_iter_matches_sample() {
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        let mut a: Rng = Rng::with_seed(22);
+        let mut b: Rng = Rng::with_seed(22);
+
+        let from_iter: Vec<f64> = normal.sample_iter(&mut a).take(10).collect();
+        let one_by_one: Vec<f64> = (0..10).map(|_| b.sample(&normal)).collect();
+        assert_eq!(from_iter, one_by_one);
+
+        let exp = Exponential::new(2.0).unwrap();
+        let small: Vec<f32> = a.sample_iter(exp).take(5).collect();
+        assert_eq!(small.len(), 5);
+        assert!(small.iter().all(|v| *v >= 0.0));
+    }
+}
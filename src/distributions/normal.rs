@@ -0,0 +1,210 @@
+use super::ziggurat::{self, Table};
+use super::Distribution;
+use crate::base::Rng;
+use crate::engine::Engine;
+use crate::error::Error;
+use crate::interval::{Interval, Open01, OpenClosed01};
+use std::f64::consts::PI;
+
+const R: f64 = 3.654_152_885_361_009;
+const V: f64 = 0.004_928_673_233_99;
+
+lazy_static! {
+    static ref TABLE: Table = Table::new(R, V, pdf, pdf_inv);
+}
+
+fn pdf(x: f64) -> f64 {
+    (-x * x / 2.0).exp()
+}
+
+fn pdf_inv(y: f64) -> f64 {
+    (-2.0 * y.ln()).sqrt()
+}
+
+/// A draw from N(0, 1) through the Ziggurat.
+pub(crate) fn standard<E: Engine>(rng: &mut Rng<E>) -> f64 {
+    ziggurat::sample(rng, &TABLE, true, pdf, |rng, negative| {
+        // Marsaglia's tail method for x > R
+        let mut x;
+        loop {
+            x = -Open01::f64_from_bits(rng.next_u64()).ln() / R;
+            let y = -Open01::f64_from_bits(rng.next_u64()).ln();
+            if 2.0 * y >= x * x {
+                break;
+            }
+        }
+        if negative {
+            -R - x
+        } else {
+            R + x
+        }
+    })
+}
+
+/// The normal distribution N(mean, std_dev²).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Normal {
+    mean: f64,
+    std_dev: f64,
+}
+
+impl Normal {
+    pub fn new(mean: f64, std_dev: f64) -> Result<Normal, Error> {
+        if !mean.is_finite() || !std_dev.is_finite() {
+            return Err(Error::NonFiniteFloat);
+        }
+        if std_dev < 0.0 {
+            return Err(Error::InvalidParameter);
+        }
+        Ok(Normal { mean, std_dev })
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    pub fn std_dev(&self) -> f64 {
+        self.std_dev
+    }
+
+    /// Box–Muller, slower than `sample` and kept as a reference to check it against.
+    pub fn sample_box_muller<E: Engine>(&self, rng: &mut Rng<E>) -> f64 {
+        let u1 = OpenClosed01::f64_from_bits(rng.next_u64());
+        let u2 = rng.next_f64();
+        let z = (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos();
+        self.mean + self.std_dev * z
+    }
+}
+
+impl Distribution<f64> for Normal {
+    fn sample<E: Engine>(&self, rng: &mut Rng<E>) -> f64 {
+        self.mean + self.std_dev * standard(rng)
+    }
+}
+
+/// The distribution of `exp(X)` where `X` is N(mu, sigma²).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct LogNormal {
+    normal: Normal,
+}
+
+impl LogNormal {
+    pub fn new(mu: f64, sigma: f64) -> Result<LogNormal, Error> {
+        Ok(LogNormal {
+            normal: Normal::new(mu, sigma)?,
+        })
+    }
+}
+
+impl Distribution<f64> for LogNormal {
+    fn sample<E: Engine>(&self, rng: &mut Rng<E>) -> f64 {
+        Distribution::<f64>::sample(&self.normal, rng).exp()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distributions::moments;
+
+    #[test]
+    fn table_matches_reference() {
+        // Values from the tables of Marsaglia and Tsang's 256 layer Ziggurat
+        assert!((TABLE.x[0] - 3.910_757_959_537_09).abs() < 1e-9);
+        assert!((TABLE.x[2] - 3.449_278_298_560_964).abs() < 1e-9);
+        assert!((TABLE.x[255] - 0.215_241_895_913_273_8).abs() < 1e-9);
+        assert!((TABLE.f[0] - 0.000_477_467_764_586_655).abs() < 1e-12);
+        assert!((TABLE.f[255] - 0.977_101_701_282_731_3).abs() < 1e-9);
+        assert_eq!(TABLE.f[256], 1.0);
+        // The top layer closes on 0 with the same area as the others
+        assert!((TABLE.x[255] * (1.0 - TABLE.f[255]) - V).abs() < 1e-10);
+    }
+
+    #[test]
+    fn ziggurat_moments() {
+        let mut rng: Rng = Rng::with_seed(21);
+        let normal = Normal::new(10.0, 2.0).unwrap();
+        let values: Vec<f64> = (0..200_000).map(|_| normal.sample(&mut rng)).collect();
+        let (mean, variance) = moments(&values);
+        assert!((mean - 10.0).abs() < 0.02, "{}", mean);
+        assert!((variance - 4.0).abs() < 0.05, "{}", variance);
+
+        // The 68-95 rule
+        let within = |k: f64| {
+            values
+                .iter()
+                .filter(|v| (*v - 10.0).abs() < 2.0 * k)
+                .count() as f64
+                / values.len() as f64
+        };
+        assert!((within(1.0) - 0.6827).abs() < 0.005, "{}", within(1.0));
+        assert!((within(2.0) - 0.9545).abs() < 0.003, "{}", within(2.0));
+    }
+
+    #[test]
+    fn box_muller_agrees() {
+        let mut rng: Rng = Rng::with_seed(21);
+        let normal = Normal::new(-3.0, 0.5).unwrap();
+        let values: Vec<f64> = (0..200_000)
+            .map(|_| normal.sample_box_muller(&mut rng))
+            .collect();
+        let (mean, variance) = moments(&values);
+        assert!((mean + 3.0).abs() < 0.01, "{}", mean);
+        assert!((variance - 0.25).abs() < 0.005, "{}", variance);
+
+        // Both paths must put the same mass in each band
+        let zig: Vec<f64> = (0..200_000).map(|_| normal.sample(&mut rng)).collect();
+        for edge in [-4.0, -3.5, -3.0, -2.5, -2.0].iter() {
+            let a = values.iter().filter(|v| *v < edge).count() as f64 / 200_000.0;
+            let b = zig.iter().filter(|v| *v < edge).count() as f64 / 200_000.0;
+            assert!((a - b).abs() < 0.006, "{} {} {}", edge, a, b);
+        }
+    }
+
+    #[test]
+    fn tail_is_reached() {
+        let mut rng: Rng = Rng::with_seed(21);
+        // P(|X| > R) is about 2.6e-4
+        let tail = (0..1_000_000)
+            .map(|_| standard(&mut rng))
+            .filter(|v| v.abs() > R)
+            .count();
+        assert!((150..400).contains(&tail), "{}", tail);
+    }
+
+    #[test]
+    fn zero_std_dev() {
+        let mut rng: Rng = Rng::with_seed(21);
+        let normal = Normal::new(1.5, 0.0).unwrap();
+        let value: f64 = normal.sample(&mut rng);
+        assert_eq!(value, 1.5);
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        assert_eq!(Normal::new(0.0, -1.0), Err(Error::InvalidParameter));
+        assert_eq!(Normal::new(f64::NAN, 1.0), Err(Error::NonFiniteFloat));
+        assert_eq!(
+            LogNormal::new(0.0, f64::INFINITY),
+            Err(Error::NonFiniteFloat)
+        );
+    }
+
+    #[test]
+    fn log_normal() {
+        let mut rng: Rng = Rng::with_seed(21);
+        let log_normal = LogNormal::new(1.0, 0.5).unwrap();
+        let values: Vec<f64> = (0..200_000).map(|_| log_normal.sample(&mut rng)).collect();
+        assert!(values.iter().all(|v| *v > 0.0));
+
+        // The mean is exp(mu + sigma²/2), the median exp(mu)
+        let (mean, _) = moments(&values);
+        let expected = (1.0f64 + 0.125).exp();
+        assert!((mean - expected).abs() < 0.02, "{}", mean);
+        let below = values.iter().filter(|v| **v < 1.0f64.exp()).count() as f64 / 200_000.0;
+        assert!((below - 0.5).abs() < 0.005, "{}", below);
+
+        let value: f32 = log_normal.sample(&mut rng);
+        assert!(value > 0.0);
+    }
+}
@@ -0,0 +1,64 @@
+use super::Distribution;
+use crate::base::Rng;
+use crate::engine::Engine;
+use crate::error::Error;
+use crate::interval::{Interval, OpenClosed01};
+
+/// The Pareto distribution, values from `scale` up with tail index `shape`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Pareto {
+    scale: f64,
+    inv_neg_shape: f64,
+}
+
+impl Pareto {
+    pub fn new(scale: f64, shape: f64) -> Result<Pareto, Error> {
+        if !scale.is_finite() || !shape.is_finite() {
+            return Err(Error::NonFiniteFloat);
+        }
+        if scale <= 0.0 || shape <= 0.0 {
+            return Err(Error::InvalidParameter);
+        }
+        Ok(Pareto {
+            scale,
+            inv_neg_shape: -1.0 / shape,
+        })
+    }
+}
+
+impl Distribution<f64> for Pareto {
+    fn sample<E: Engine>(&self, rng: &mut Rng<E>) -> f64 {
+        let u = OpenClosed01::f64_from_bits(rng.next_u64());
+        self.scale * u.powf(self.inv_neg_shape)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distributions::moments;
+
+    #[test]
+    fn pareto_moments() {
+        let mut rng: Rng = Rng::with_seed(22);
+        let pareto = Pareto::new(2.0, 5.0).unwrap();
+        let values: Vec<f64> = pareto.sample_iter(&mut rng).take(200_000).collect();
+        assert!(values.iter().all(|v| *v >= 2.0));
+
+        // Mean α·x/(α - 1), variance x²·α/((α - 1)²(α - 2))
+        let (mean, variance) = moments(&values);
+        assert!((mean - 2.5).abs() < 0.01, "{}", mean);
+        assert!((variance - 4.0 * 5.0 / 48.0).abs() < 0.03, "{}", variance);
+
+        // P(X > t) = (x/t)^α
+        let above = values.iter().filter(|v| **v > 4.0).count() as f64 / 200_000.0;
+        assert!((above - 0.5f64.powi(5)).abs() < 0.002, "{}", above);
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        assert_eq!(Pareto::new(0.0, 1.0), Err(Error::InvalidParameter));
+        assert_eq!(Pareto::new(1.0, -3.0), Err(Error::InvalidParameter));
+        assert_eq!(Pareto::new(f64::NAN, 1.0), Err(Error::NonFiniteFloat));
+    }
+}
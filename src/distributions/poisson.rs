@@ -0,0 +1,149 @@
+use super::math::ln_gamma;
+use super::Distribution;
+use crate::base::Rng;
+use crate::engine::Engine;
+use crate::error::Error;
+
+/// The Poisson distribution, the number of events in an interval with `lambda` expected.
+/// `lambda` 0 is the point mass at 0.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Poisson {
+    lambda: f64,
+}
+
+impl Poisson {
+    pub fn new(lambda: f64) -> Result<Poisson, Error> {
+        if !lambda.is_finite() {
+            return Err(Error::NonFiniteFloat);
+        }
+        if lambda < 0.0 {
+            return Err(Error::InvalidParameter);
+        }
+        Ok(Poisson { lambda })
+    }
+
+    pub fn lambda(&self) -> f64 {
+        self.lambda
+    }
+}
+
+impl Distribution<u64> for Poisson {
+    fn sample<E: Engine>(&self, rng: &mut Rng<E>) -> u64 {
+        sample(rng, self.lambda)
+    }
+}
+
+/// Inversion below `lambda` 10, PTRS above it.
+pub(crate) fn sample<E: Engine>(rng: &mut Rng<E>, lambda: f64) -> u64 {
+    if lambda <= 0.0 {
+        0
+    } else if lambda < 10.0 {
+        inversion(rng, lambda)
+    } else {
+        ptrs(rng, lambda)
+    }
+}
+
+// Walks up the CDF, about lambda steps on average
+fn inversion<E: Engine>(rng: &mut Rng<E>, lambda: f64) -> u64 {
+    let mut u = rng.next_f64();
+    let mut k = 0;
+    let mut p = (-lambda).exp();
+    while u > p {
+        u -= p;
+        k += 1;
+        p *= lambda / k as f64;
+        // Rounding can leave u above a vanishing p, the mass left there is negligible
+        if p < f64::MIN_POSITIVE {
+            break;
+        }
+    }
+    k
+}
+
+// Hörmann, "The transformed rejection method for generating Poisson random variables"
+fn ptrs<E: Engine>(rng: &mut Rng<E>, lambda: f64) -> u64 {
+    let ln_lambda = lambda.ln();
+    let b = 0.931 + 2.53 * lambda.sqrt();
+    let a = -0.059 + 0.02483 * b;
+    let inv_alpha = 1.1239 + 1.1328 / (b - 3.4);
+    let v_r = 0.9277 - 3.6224 / (b - 2.0);
+
+    loop {
+        let u = rng.next_f64() - 0.5;
+        let v = rng.next_f64();
+        let us = 0.5 - u.abs();
+        let k = ((2.0 * a / us + b) * u + lambda + 0.43).floor();
+
+        // The squeeze accepts most draws without a logarithm
+        if us >= 0.07 && v <= v_r {
+            return k as u64;
+        }
+        if k < 0.0 || (us < 0.013 && v > us) {
+            continue;
+        }
+        let accept = (v * inv_alpha / (a / (us * us) + b)).ln();
+        if accept <= -lambda + k * ln_lambda - ln_gamma(k + 1.0) {
+            return k as u64;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distributions::{counts, moments};
+
+    #[test]
+    fn poisson_moments() {
+        // Both algorithms, the mean and the variance are lambda
+        for lambda in [0.5, 3.0, 9.9, 10.0, 50.0, 1_000.0, 1e6].iter() {
+            let values: Vec<f64> = counts(&Poisson::new(*lambda).unwrap(), 100_000);
+            let (mean, variance) = moments(&values);
+            assert!(
+                (mean - lambda).abs() < 0.02 * lambda.sqrt(),
+                "{} {}",
+                lambda,
+                mean
+            );
+            assert!(
+                (variance - lambda).abs() < 0.03 * lambda,
+                "{} {}",
+                lambda,
+                variance
+            );
+        }
+    }
+
+    #[test]
+    fn ptrs_matches_pmf() {
+        let lambda = 30.0f64;
+        let values: Vec<f64> = counts(&Poisson::new(lambda).unwrap(), 200_000);
+        for k in [15.0, 25.0, 30.0, 35.0, 45.0].iter() {
+            let pmf = (-lambda + k * lambda.ln() - ln_gamma(k + 1.0)).exp();
+            let seen = values.iter().filter(|v| *v == k).count() as f64 / 200_000.0;
+            assert!((seen - pmf).abs() < 0.1 * pmf, "{} {} {}", k, seen, pmf);
+        }
+    }
+
+    #[test]
+    fn integer_types() {
+        let mut rng: Rng = Rng::with_seed(23);
+        let poisson = Poisson::new(4.0).unwrap();
+        let a: u32 = poisson.sample(&mut rng);
+        let b: usize = rng.sample(&poisson);
+        assert!(a < 100 && b < 100);
+    }
+
+    #[test]
+    fn zero_lambda() {
+        let values = counts(&Poisson::new(0.0).unwrap(), 1_000);
+        assert!(values.iter().all(|v| *v == 0.0));
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        assert_eq!(Poisson::new(-1.0), Err(Error::InvalidParameter));
+        assert_eq!(Poisson::new(f64::INFINITY), Err(Error::NonFiniteFloat));
+    }
+}
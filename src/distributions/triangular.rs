@@ -0,0 +1,77 @@
+use super::Distribution;
+use crate::base::Rng;
+use crate::engine::Engine;
+use crate::error::Error;
+
+/// The triangular distribution on [`min`, `max`] peaking at `mode`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Triangular {
+    min: f64,
+    max: f64,
+    mode: f64,
+}
+
+impl Triangular {
+    pub fn new(min: f64, max: f64, mode: f64) -> Result<Triangular, Error> {
+        if !min.is_finite() || !max.is_finite() || !mode.is_finite() {
+            return Err(Error::NonFiniteFloat);
+        }
+        if min >= max {
+            return Err(Error::EmptyRange);
+        }
+        if mode < min || mode > max {
+            return Err(Error::InvalidParameter);
+        }
+        Ok(Triangular { min, max, mode })
+    }
+}
+
+impl Distribution<f64> for Triangular {
+    fn sample<E: Engine>(&self, rng: &mut Rng<E>) -> f64 {
+        // Inverse CDF, split at F(mode)
+        let u = rng.next_f64();
+        let width = self.max - self.min;
+        let left = self.mode - self.min;
+        if u * width < left {
+            self.min + (u * width * left).sqrt()
+        } else {
+            self.max - ((1.0 - u) * width * (self.max - self.mode)).sqrt()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distributions::moments;
+
+    #[test]
+    fn triangular_moments() {
+        let mut rng: Rng = Rng::with_seed(22);
+        for (a, b, c) in [(1.0, 4.0, 2.0), (0.0, 1.0, 0.0), (-2.0, 2.0, 2.0)].iter() {
+            let triangular = Triangular::new(*a, *b, *c).unwrap();
+            let values: Vec<f64> = triangular.sample_iter(&mut rng).take(200_000).collect();
+            assert!(values.iter().all(|v| v >= a && v <= b));
+
+            // Mean (a + b + c)/3, variance (a² + b² + c² - ab - ac - bc)/18
+            let (mean, variance) = moments(&values);
+            let expected_variance = (a * a + b * b + c * c - a * b - a * c - b * c) / 18.0;
+            assert!((mean - (a + b + c) / 3.0).abs() < 0.01, "{}", mean);
+            assert!(
+                (variance - expected_variance).abs() < 0.02 * expected_variance,
+                "{}",
+                variance
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        assert_eq!(Triangular::new(1.0, 1.0, 1.0), Err(Error::EmptyRange));
+        assert_eq!(Triangular::new(0.0, 1.0, 2.0), Err(Error::InvalidParameter));
+        assert_eq!(
+            Triangular::new(0.0, f64::INFINITY, 1.0),
+            Err(Error::NonFiniteFloat)
+        );
+    }
+}
@@ -0,0 +1,71 @@
+use super::Distribution;
+use crate::base::Rng;
+use crate::engine::Engine;
+use crate::error::Error;
+use crate::interval::{Interval, OpenClosed01};
+
+/// The Weibull distribution with `scale` λ and `shape` k.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Weibull {
+    scale: f64,
+    inv_shape: f64,
+}
+
+impl Weibull {
+    pub fn new(scale: f64, shape: f64) -> Result<Weibull, Error> {
+        if !scale.is_finite() || !shape.is_finite() {
+            return Err(Error::NonFiniteFloat);
+        }
+        if scale <= 0.0 || shape <= 0.0 {
+            return Err(Error::InvalidParameter);
+        }
+        Ok(Weibull {
+            scale,
+            inv_shape: 1.0 / shape,
+        })
+    }
+}
+
+impl Distribution<f64> for Weibull {
+    fn sample<E: Engine>(&self, rng: &mut Rng<E>) -> f64 {
+        let u = OpenClosed01::f64_from_bits(rng.next_u64());
+        self.scale * (-u.ln()).powf(self.inv_shape)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distributions::moments;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn weibull_moments() {
+        let mut rng: Rng = Rng::with_seed(22);
+
+        // Shape 2 is the Rayleigh distribution, mean λ·√π/2 and variance λ²(1 - π/4)
+        let weibull = Weibull::new(3.0, 2.0).unwrap();
+        let values: Vec<f64> = weibull.sample_iter(&mut rng).take(200_000).collect();
+        let (mean, variance) = moments(&values);
+        assert!((mean - 1.5 * PI.sqrt()).abs() < 0.01, "{}", mean);
+        assert!(
+            (variance - 9.0 * (1.0 - PI / 4.0)).abs() < 0.03,
+            "{}",
+            variance
+        );
+
+        // Shape 1 is Exp(1/λ)
+        let weibull = Weibull::new(0.5, 1.0).unwrap();
+        let values: Vec<f64> = weibull.sample_iter(&mut rng).take(200_000).collect();
+        let (mean, variance) = moments(&values);
+        assert!((mean - 0.5).abs() < 0.005, "{}", mean);
+        assert!((variance - 0.25).abs() < 0.006, "{}", variance);
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        assert_eq!(Weibull::new(1.0, 0.0), Err(Error::InvalidParameter));
+        assert_eq!(Weibull::new(-1.0, 1.0), Err(Error::InvalidParameter));
+        assert_eq!(Weibull::new(1.0, f64::INFINITY), Err(Error::NonFiniteFloat));
+    }
+}
@@ -0,0 +1,221 @@
+use super::Distribution;
+use crate::base::{RandomableRange, Rng};
+use crate::engine::Engine;
+use crate::error::Error;
+
+/// Integer and float types usable as weights by `WeightedIndex` and `AliasTable`.
+pub trait Weight: RandomableRange {
+    /// Finite and not negative.
+    fn is_valid(self) -> bool {
+        self >= Self::ZERO
+    }
+
+    /// `None` when the sum no longer fits, or is infinite for floats.
+    fn checked_add(self, other: Self) -> Option<Self>;
+
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! int_weight {
+    ($($ty:ty),+) => {
+        $(
+            impl Weight for $ty {
+                fn checked_add(self, other: $ty) -> Option<$ty> {
+                    <$ty>::checked_add(self, other)
+                }
+
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )+
+    };
+}
+
+int_weight!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+macro_rules! float_weight {
+    ($($ty:ty),+) => {
+        $(
+            impl Weight for $ty {
+                fn is_valid(self) -> bool {
+                    self.is_finite() && self >= 0.0
+                }
+
+                fn checked_add(self, other: $ty) -> Option<$ty> {
+                    Some(self + other).filter(|sum| sum.is_finite())
+                }
+
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )+
+    };
+}
+
+float_weight!(f32, f64);
+
+// Running sums of `weights`, the last one being the total
+pub(super) fn cumulative<W: Weight>(weights: &[W]) -> Result<Vec<W>, Error> {
+    if weights.is_empty() {
+        return Err(Error::EmptyRange);
+    }
+    let mut total = W::ZERO;
+    let mut sums = Vec::with_capacity(weights.len());
+    for weight in weights.iter() {
+        if !weight.is_valid() {
+            return Err(Error::InvalidWeight);
+        }
+        total = total.checked_add(*weight).ok_or(Error::InvalidWeight)?;
+        sums.push(total);
+    }
+    if total == W::ZERO {
+        // Every weight is 0, nothing can be picked
+        return Err(Error::EmptyRange);
+    }
+    Ok(sums)
+}
+
+/// Picks an index with probability proportional to its weight, by binary search
+/// over the running sums. Weights can be changed later with `update_weights`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeightedIndex<W: Weight> {
+    weights: Vec<W>,
+    cumulative: Vec<W>,
+}
+
+impl<W: Weight> WeightedIndex<W> {
+    /// Fails with `Error::InvalidWeight` for a negative, infinite or NaN weight or
+    /// an overflowing total, and with `Error::EmptyRange` when no weight is above 0.
+    pub fn new<I: IntoIterator<Item = W>>(weights: I) -> Result<WeightedIndex<W>, Error> {
+        let weights: Vec<W> = weights.into_iter().collect();
+        let cumulative = cumulative(&weights)?;
+        Ok(WeightedIndex {
+            weights,
+            cumulative,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.weights.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.weights.is_empty()
+    }
+
+    pub fn weight(&self, index: usize) -> Option<W> {
+        self.weights.get(index).copied()
+    }
+
+    pub fn total(&self) -> W {
+        self.cumulative[self.cumulative.len() - 1]
+    }
+
+    /// Sets each `(index, weight)` pair. Nothing changes if any of them is invalid,
+    /// an index out of bounds being `Error::InvalidParameter`.
+    pub fn update_weights(&mut self, updates: &[(usize, W)]) -> Result<(), Error> {
+        let mut weights = self.weights.clone();
+        for (index, weight) in updates.iter() {
+            match weights.get_mut(*index) {
+                Some(slot) => *slot = *weight,
+                None => return Err(Error::InvalidParameter),
+            }
+        }
+        self.cumulative = cumulative(&weights)?;
+        self.weights = weights;
+        Ok(())
+    }
+}
+
+impl<W: Weight> Distribution<usize> for WeightedIndex<W> {
+    fn sample<E: Engine>(&self, rng: &mut Rng<E>) -> usize {
+        // The first running sum above x, so a weight of 0 is never picked
+        let x = W::randn(rng, self.total());
+        self.cumulative.partition_point(|sum| *sum <= x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frequencies<D: Distribution<usize>>(distribution: &D, len: usize, n: usize) -> Vec<f64> {
+        let mut rng: Rng = Rng::with_seed(24);
+        let mut seen = vec![0usize; len];
+        for _ in 0..n {
+            seen[distribution.sample(&mut rng)] += 1;
+        }
+        seen.iter().map(|count| *count as f64 / n as f64).collect()
+    }
+
+    #[test]
+    fn integer_weights() {
+        let index = WeightedIndex::new(vec![70u32, 25, 0, 5]).unwrap();
+        assert_eq!(index.total(), 100);
+        let seen = frequencies(&index, 4, 100_000);
+        for (seen, expected) in seen.iter().zip([0.7, 0.25, 0.0, 0.05].iter()) {
+            assert!((seen - expected).abs() < 0.005, "{} {}", seen, expected);
+        }
+        assert_eq!(seen[2], 0.0);
+    }
+
+    #[test]
+    fn float_weights() {
+        let index = WeightedIndex::new(vec![0.5, 1.5, 2.0f64]).unwrap();
+        let seen = frequencies(&index, 3, 100_000);
+        for (seen, expected) in seen.iter().zip([0.125, 0.375, 0.5].iter()) {
+            assert!((seen - expected).abs() < 0.005, "{} {}", seen, expected);
+        }
+
+        let single = WeightedIndex::new(vec![0.1f32]).unwrap();
+        assert_eq!(frequencies(&single, 1, 100), vec![1.0]);
+    }
+
+    #[test]
+    fn update_weights() {
+        let mut index = WeightedIndex::new(vec![1i64, 1, 1]).unwrap();
+        index.update_weights(&[(0, 0), (2, 8)]).unwrap();
+        assert_eq!(index.weight(2), Some(8));
+        assert_eq!(index.total(), 9);
+        let seen = frequencies(&index, 3, 90_000);
+        assert_eq!(seen[0], 0.0);
+        assert!((seen[1] - 1.0 / 9.0).abs() < 0.005, "{}", seen[1]);
+
+        // A failed update leaves the weights as they were
+        assert_eq!(
+            index.update_weights(&[(1, 5), (2, -1)]),
+            Err(Error::InvalidWeight)
+        );
+        assert_eq!(
+            index.update_weights(&[(3, 1)]),
+            Err(Error::InvalidParameter)
+        );
+        assert_eq!(
+            index.update_weights(&[(1, 0), (2, 0)]),
+            Err(Error::EmptyRange)
+        );
+        assert_eq!(index.weight(1), Some(1));
+        assert_eq!(index.total(), 9);
+    }
+
+    #[test]
+    fn invalid_weights() {
+        assert_eq!(WeightedIndex::new(Vec::<u8>::new()), Err(Error::EmptyRange));
+        assert_eq!(WeightedIndex::new(vec![0u8, 0]), Err(Error::EmptyRange));
+        assert_eq!(WeightedIndex::new(vec![3, -1]), Err(Error::InvalidWeight));
+        assert_eq!(
+            WeightedIndex::new(vec![1.0, f64::NAN]),
+            Err(Error::InvalidWeight)
+        );
+        assert_eq!(
+            WeightedIndex::new(vec![f64::MAX, f64::MAX]),
+            Err(Error::InvalidWeight)
+        );
+        assert_eq!(
+            WeightedIndex::new(vec![200u8, 100]),
+            Err(Error::InvalidWeight)
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use crate::base::Rng;
+use crate::engine::Engine;
+use crate::interval::{Interval, Open01};
+
+const LAYERS: usize = 256;
+
+// https://www.jstatsoft.org/article/view/v005i08, with 256 layers of equal area `v`
+pub struct Table {
+    pub x: [f64; LAYERS + 1],
+    pub f: [f64; LAYERS + 1],
+}
+
+impl Table {
+    /// `r` is where the tail starts, `pdf` the unnormalized density and
+    /// `pdf_inv` its inverse on the positive half.
+    pub fn new(r: f64, v: f64, pdf: fn(f64) -> f64, pdf_inv: fn(f64) -> f64) -> Table {
+        let mut x = [0.0; LAYERS + 1];
+        // The base layer is the strip below r plus the tail, as a rectangle of area v
+        x[0] = v / pdf(r);
+        x[1] = r;
+        for i in 1..LAYERS - 1 {
+            x[i + 1] = pdf_inv(v / x[i] + pdf(x[i]));
+        }
+        x[LAYERS] = 0.0;
+
+        let mut f = [0.0; LAYERS + 1];
+        for (f, x) in f.iter_mut().zip(x.iter()) {
+            *f = pdf(*x);
+        }
+        Table { x, f }
+    }
+}
+
+/// Draws from the density described by `table`, mirrored around 0 when `symmetric`.
+/// `tail` samples beyond `x[1]` and receives whether the draw is negative.
+pub fn sample<E: Engine>(
+    rng: &mut Rng<E>,
+    table: &Table,
+    symmetric: bool,
+    pdf: fn(f64) -> f64,
+    mut tail: impl FnMut(&mut Rng<E>, bool) -> f64,
+) -> f64 {
+    loop {
+        // The low byte picks the layer, the high bits the position inside it
+        let bits = rng.next_u64();
+        let i = (bits & 0xff) as usize;
+        let u = if symmetric {
+            2.0 * Open01::f64_from_bits(bits) - 1.0
+        } else {
+            Open01::f64_from_bits(bits)
+        };
+
+        let x = u * table.x[i];
+        if x.abs() < table.x[i + 1] {
+            return x;
+        }
+        if i == 0 {
+            return tail(rng, u < 0.0);
+        }
+        let y = table.f[i + 1] + (table.f[i] - table.f[i + 1]) * rng.next_f64();
+        if y < pdf(x) {
+            return x;
+        }
+    }
+}
@@ -0,0 +1,261 @@
+use super::Distribution;
+use crate::base::Rng;
+use crate::engine::Engine;
+use crate::error::Error;
+use crate::interval::{Interval, OpenClosed01};
+
+// Hörmann and Derflinger, "Rejection-inversion to generate variates from monotone
+// discrete distributions", over the integers of [min, max] with weights k^-s.
+// Nothing is tabulated, so max can be huge or infinite.
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct RejectionInversion {
+    s: f64,
+    min: f64,
+    max: f64,
+    h_integral_min: f64,
+    h_integral_max: f64,
+    squeeze: f64,
+}
+
+impl RejectionInversion {
+    // An infinite max needs s > 1
+    fn new(s: f64, min: f64, max: f64) -> RejectionInversion {
+        let h_integral_max = if max.is_finite() {
+            h_integral(max + 0.5, s)
+        } else {
+            1.0 / (s - 1.0)
+        };
+        RejectionInversion {
+            s,
+            min,
+            max,
+            h_integral_min: h_integral(min + 0.5, s) - h(min, s),
+            h_integral_max,
+            squeeze: min + 1.0 - h_integral_inverse(h_integral(min + 1.5, s) - h(min + 1.0, s), s),
+        }
+    }
+
+    fn sample<E: Engine>(&self, rng: &mut Rng<E>) -> u64 {
+        loop {
+            let r = OpenClosed01::f64_from_bits(rng.next_u64());
+            let u = self.h_integral_max + r * (self.h_integral_min - self.h_integral_max);
+            let x = h_integral_inverse(u, self.s);
+            let k = (x + 0.5).floor().max(self.min).min(self.max);
+
+            // The squeeze accepts most draws without evaluating h
+            if k - x <= self.squeeze || u >= h_integral(k + 0.5, self.s) - h(k, self.s) {
+                // The cast saturates for the far tail of an unbounded power law
+                return k as u64;
+            }
+        }
+    }
+}
+
+fn h(x: f64, s: f64) -> f64 {
+    (-s * x.ln()).exp()
+}
+
+// The integral of h from 1 to x, (x^(1 - s) - 1)/(1 - s), continuous at s = 1
+fn h_integral(x: f64, s: f64) -> f64 {
+    let ln_x = x.ln();
+    exp_m1_div((1.0 - s) * ln_x) * ln_x
+}
+
+fn h_integral_inverse(x: f64, s: f64) -> f64 {
+    let t = (x * (1.0 - s)).max(-1.0);
+    (ln_1p_div(t) * x).exp()
+}
+
+// (e^x - 1)/x and ln(1 + x)/x, both 1 at 0
+fn exp_m1_div(x: f64) -> f64 {
+    if x.abs() > 1e-8 {
+        x.exp_m1() / x
+    } else {
+        1.0 + x / 2.0 * (1.0 + x / 3.0)
+    }
+}
+
+fn ln_1p_div(x: f64) -> f64 {
+    if x.abs() > 1e-8 {
+        x.ln_1p() / x
+    } else {
+        1.0 - x * (0.5 - x / 3.0)
+    }
+}
+
+/// The Zipf distribution over 1..=n, k drawn with probability proportional to k^-s.
+/// Sampling takes constant expected time and no memory whatever `n` is.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Zipf {
+    n: u64,
+    sampler: RejectionInversion,
+}
+
+impl Zipf {
+    pub fn new(n: u64, s: f64) -> Result<Zipf, Error> {
+        if !s.is_finite() {
+            return Err(Error::NonFiniteFloat);
+        }
+        if n == 0 || s < 0.0 {
+            return Err(Error::InvalidParameter);
+        }
+        Ok(Zipf {
+            n,
+            sampler: RejectionInversion::new(s, 1.0, n as f64),
+        })
+    }
+
+    pub fn n(&self) -> u64 {
+        self.n
+    }
+
+    pub fn s(&self) -> f64 {
+        self.sampler.s
+    }
+}
+
+impl Distribution<u64> for Zipf {
+    fn sample<E: Engine>(&self, rng: &mut Rng<E>) -> u64 {
+        // Above 2^53 `n as f64` can round up past n
+        self.sampler.sample(rng).min(self.n)
+    }
+}
+
+/// The unbounded discrete power law, k >= `min` drawn with probability proportional to k^-alpha.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PowerLaw {
+    min: u64,
+    sampler: RejectionInversion,
+}
+
+impl PowerLaw {
+    /// `alpha` must be above 1 for the weights to sum up, and `min` at least 1.
+    pub fn new(alpha: f64, min: u64) -> Result<PowerLaw, Error> {
+        if !alpha.is_finite() {
+            return Err(Error::NonFiniteFloat);
+        }
+        if alpha <= 1.0 || min == 0 {
+            return Err(Error::InvalidParameter);
+        }
+        Ok(PowerLaw {
+            min,
+            sampler: RejectionInversion::new(alpha, min as f64, f64::INFINITY),
+        })
+    }
+
+    pub fn alpha(&self) -> f64 {
+        self.sampler.s
+    }
+
+    pub fn min(&self) -> u64 {
+        self.min
+    }
+}
+
+impl Distribution<u64> for PowerLaw {
+    fn sample<E: Engine>(&self, rng: &mut Rng<E>) -> u64 {
+        self.sampler.sample(rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distributions::{counts, moments};
+
+    // P(k) for k^-s over [min, max], normalized by summing
+    fn pmf(k: u64, s: f64, min: u64, max: u64) -> f64 {
+        let total: f64 = (min..=max).map(|i| (i as f64).powf(-s)).sum();
+        (k as f64).powf(-s) / total
+    }
+
+    #[test]
+    fn zipf_matches_pmf() {
+        for s in [1.0, 0.5, 1.5, 3.0].iter() {
+            let values = counts(&Zipf::new(1_000, *s).unwrap(), 200_000);
+            assert!(values.iter().all(|v| (1.0..=1_000.0).contains(v)));
+            for k in [1, 2, 3, 10, 50].iter() {
+                let expected = pmf(*k, *s, 1, 1_000);
+                if expected < 1e-3 {
+                    continue;
+                }
+                let seen = values.iter().filter(|v| **v == *k as f64).count() as f64 / 200_000.0;
+                assert!(
+                    (seen - expected).abs() < 4.0 * (expected / 200_000.0).sqrt(),
+                    "{} {} {} {}",
+                    s,
+                    k,
+                    seen,
+                    expected
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn zipf_edge_cases() {
+        // s = 0 is uniform
+        let values = counts(&Zipf::new(10, 0.0).unwrap(), 100_000);
+        let (mean, _) = moments(&values);
+        assert!((mean - 5.5).abs() < 0.03, "{}", mean);
+
+        assert!(counts(&Zipf::new(1, 2.0).unwrap(), 100)
+            .iter()
+            .all(|v| *v == 1.0));
+
+        // A trillion keys, the hottest one still gets 1/H(n) of the draws
+        let values = counts(&Zipf::new(1_000_000_000_000, 1.0).unwrap(), 200_000);
+        let harmonic = (1e12f64).ln() + 0.577_215_664_901_532_9;
+        let first = values.iter().filter(|v| **v == 1.0).count() as f64 / 200_000.0;
+        assert!((first - 1.0 / harmonic).abs() < 0.002, "{}", first);
+        assert!(values.iter().any(|v| *v > 1e9));
+    }
+
+    #[test]
+    fn zipf_stays_below_huge_n() {
+        // Both round up as f64, to 2^60 + 256 and to 2^64
+        for n in [(1u64 << 60) + 129, u64::MAX - 1].iter() {
+            let zipf = Zipf::new(*n, 0.0).unwrap();
+            assert!(zipf.sampler.max as u128 > *n as u128);
+            let mut rng: Rng = Rng::with_seed(23);
+            for _ in 0..10_000 {
+                let value: u64 = zipf.sample(&mut rng);
+                assert!((1..=*n).contains(&value), "{} {}", n, value);
+            }
+        }
+    }
+
+    #[test]
+    fn power_law_matches_pmf() {
+        for (alpha, min) in [(2.5, 1), (2.0, 5), (1.5, 100)].iter() {
+            let values = counts(&PowerLaw::new(*alpha, *min).unwrap(), 200_000);
+            assert!(values.iter().all(|v| *v >= *min as f64));
+
+            // The tail beyond 10^6 is added as an integral
+            let max = 1_000_000;
+            let tail = (max as f64 + 0.5).powf(1.0 - alpha) / (alpha - 1.0);
+            let sum: f64 = (*min..=max).map(|i| (i as f64).powf(-alpha)).sum();
+            for k in [*min, min + 1, min * 2, min * 4].iter() {
+                let expected = (*k as f64).powf(-alpha) / (sum + tail);
+                let seen = values.iter().filter(|v| **v == *k as f64).count() as f64 / 200_000.0;
+                assert!(
+                    (seen - expected).abs() < 4.0 * (expected / 200_000.0).sqrt(),
+                    "{} {} {} {}",
+                    alpha,
+                    k,
+                    seen,
+                    expected
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        assert_eq!(Zipf::new(0, 1.0), Err(Error::InvalidParameter));
+        assert_eq!(Zipf::new(10, -1.0), Err(Error::InvalidParameter));
+        assert_eq!(Zipf::new(10, f64::NAN), Err(Error::NonFiniteFloat));
+        assert_eq!(PowerLaw::new(1.0, 1), Err(Error::InvalidParameter));
+        assert_eq!(PowerLaw::new(2.0, 0), Err(Error::InvalidParameter));
+    }
+}
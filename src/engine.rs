@@ -0,0 +1,113 @@
+use crate::entropy;
+use crate::error::Error;
+use crate::seed::expand_seed;
+
+const A: i64 = 1103515245;
+const C: i16 = 12345;
+const M: u64 = 1 << 63;
+
+/// Source of raw random bits behind `Rng` and `Random`.
+pub trait Engine {
+    fn from_seed(seed: u128) -> Self
+    where
+        Self: Sized;
+
+    /// An engine seeded from the operating system, see `Rng::from_entropy`.
+    fn from_entropy() -> Self
+    where
+        Self: Sized,
+    {
+        Self::from_seed(entropy::seed())
+    }
+
+    fn next_u32(&mut self) -> u32;
+    fn next_u64(&mut self) -> u64;
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    /// `fill_bytes` for engines that can fail, e.g. ones reading from the operating system.
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+/// The linear congruential generator simplerand has always used.
+#[derive(Debug, Copy, Clone)]
+pub struct Lcg {
+    state: u128,
+}
+
+impl Lcg {
+    pub fn from_state(state: u128) -> Lcg {
+        Lcg { state }
+    }
+
+    pub fn state(&self) -> u128 {
+        self.state
+    }
+
+    fn step(&mut self) -> u128 {
+        // https://stackoverflow.com/questions/3062746/special-simple-random-number-generator
+        self.state = (A as u128 * self.state + C as u128) % M as u128;
+        self.state
+    }
+}
+
+impl Engine for Lcg {
+    fn from_seed(seed: u128) -> Lcg {
+        let mut word = [0u64; 1];
+        expand_seed(seed, &mut word);
+        Lcg::from_state(word[0] as u128 % M as u128)
+    }
+
+    fn next_u32(&mut self) -> u32 {
+        // The low bits of an LCG have short periods, use the top of the 63 bits
+        (self.step() >> 31) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        // A single step only carries 63 bits
+        let high = self.next_u32() as u64;
+        let low = self.next_u32() as u64;
+        (high << 32) | low
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lcg_sequence() {
+        let mut lcg = Lcg::from_state(1);
+        assert_eq!(lcg.step(), 1103527590);
+        assert_eq!(lcg.step(), 1217759518843121895);
+    }
+
+    #[test]
+    fn lcg_full_width() {
+        let mut lcg = Lcg::from_seed(1);
+        let top_bit_set = (0..1000).filter(|_| lcg.next_u64() >> 63 == 1).count();
+        assert!((400..600).contains(&top_bit_set), "{}", top_bit_set);
+    }
+
+    #[test]
+    fn fill_bytes_partial_chunk() {
+        let mut a = Lcg::from_seed(7);
+        let mut b = Lcg::from_seed(7);
+
+        let mut buf = [0u8; 11];
+        a.fill_bytes(&mut buf);
+
+        let first = b.next_u64().to_le_bytes();
+        let second = b.next_u64().to_le_bytes();
+        assert_eq!(&buf[..8], &first[..]);
+        assert_eq!(&buf[8..], &second[..3]);
+    }
+}
@@ -0,0 +1,98 @@
+use crate::seed::expand_seed;
+use std::fs::File;
+use std::io::{self, Read};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Tells apart the fallback seeds taken within the same clock tick
+static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+pub fn fill(dest: &mut [u8]) -> io::Result<()> {
+    getrandom(dest).or_else(|_| urandom(dest))
+}
+
+/// A seed from the operating system, or from time, pid and a counter when
+/// neither `getrandom(2)` nor `/dev/urandom` is available.
+pub fn seed() -> u128 {
+    let mut bytes = [0u8; 16];
+    match fill(&mut bytes) {
+        Ok(()) => u128::from_le_bytes(bytes),
+        Err(_) => fallback_seed(),
+    }
+}
+
+fn fallback_seed() -> u128 {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let pid = std::process::id() as u128;
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed) as u128;
+
+    let mut words = [0u64; 2];
+    expand_seed(nanos ^ pid << 64 ^ count << 96, &mut words);
+    words[0] as u128 | (words[1] as u128) << 64
+}
+
+fn urandom(dest: &mut [u8]) -> io::Result<()> {
+    File::open("/dev/urandom")?.read_exact(dest)
+}
+
+#[cfg(target_os = "linux")]
+fn getrandom(dest: &mut [u8]) -> io::Result<()> {
+    let mut filled = 0;
+    while filled < dest.len() {
+        let rest = &mut dest[filled..];
+        let read = unsafe { libc::syscall(libc::SYS_getrandom, rest.as_mut_ptr(), rest.len(), 0) };
+        if read < 0 {
+            let err = io::Error::last_os_error();
+            if err.kind() == io::ErrorKind::Interrupted {
+                continue;
+            }
+            return Err(err);
+        }
+        filled += read as usize;
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn getrandom(_dest: &mut [u8]) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Other,
+        "getrandom(2) is Linux only",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fill_from_os() {
+        let mut a = [0u8; 32];
+        let mut b = [0u8; 32];
+        fill(&mut a).unwrap();
+        fill(&mut b).unwrap();
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn urandom_fallback() {
+        let mut a = [0u8; 32];
+        let mut b = [0u8; 32];
+        urandom(&mut a).unwrap();
+        urandom(&mut b).unwrap();
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn fallback_seeds_differ_within_a_tick() {
+        let seeds: Vec<u128> = (0..100).map(|_| fallback_seed()).collect();
+        for (i, a) in seeds.iter().enumerate() {
+            for b in seeds[i + 1..].iter() {
+                assert_ne!(a, b);
+            }
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use std::error;
+use std::fmt;
+
+/// Why a `try_` function could not produce a value, or a distribution could not be built.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The range holds no value, e.g. `randn(0)` or `rand_range(5, 5)`.
+    EmptyRange,
+    /// A bound no range can be built from, e.g. a negative `n` in `randn`.
+    InvalidBound,
+    /// A float bound is infinite or NaN.
+    NonFiniteFloat,
+    /// The generator mutex was poisoned by a panicking thread.
+    Poisoned,
+    /// A distribution parameter is out of its domain, e.g. a negative standard
+    /// deviation or a `gen_bool` probability above 1.
+    InvalidParameter,
+    /// A weight is negative, infinite or NaN, or the weights overflow when summed.
+    InvalidWeight,
+    /// The engine or the operating system entropy source could not produce bytes.
+    Unavailable,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            Error::EmptyRange => "empty range, max must be bigger than min",
+            Error::InvalidBound => "invalid bound, must be bigger than 0",
+            Error::NonFiniteFloat => "float bounds must be finite",
+            Error::Poisoned => "generator lock poisoned",
+            Error::InvalidParameter => "invalid distribution parameter",
+            Error::InvalidWeight => "invalid weight, must be finite and not negative",
+            Error::Unavailable => "random source unavailable",
+        };
+        f.write_str(message)
+    }
+}
+
+impl error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            Error::EmptyRange.to_string(),
+            "empty range, max must be bigger than min"
+        );
+        assert_eq!(Error::Poisoned.to_string(), "generator lock poisoned");
+        assert_eq!(Error::Unavailable.to_string(), "random source unavailable");
+        assert_eq!(
+            Error::InvalidWeight.to_string(),
+            "invalid weight, must be finite and not negative"
+        );
+
+        let boxed: Box<dyn error::Error> = Box::new(Error::NonFiniteFloat);
+        assert_eq!(boxed.to_string(), "float bounds must be finite");
+    }
+}
@@ -0,0 +1,244 @@
+use crate::base::Rng;
+use crate::engine::Engine;
+use crate::error::Error;
+use std::convert::TryInto;
+
+/// Buffers that `fill` overwrites with random data, taking every bit of each engine output.
+pub trait Fill {
+    fn fill<E: Engine>(&mut self, rng: &mut Rng<E>);
+
+    fn try_fill<E: Engine>(&mut self, rng: &mut Rng<E>) -> Result<(), Error> {
+        self.fill(rng);
+        Ok(())
+    }
+}
+
+impl Fill for [u8] {
+    fn fill<E: Engine>(&mut self, rng: &mut Rng<E>) {
+        rng.fill_bytes(self)
+    }
+
+    fn try_fill<E: Engine>(&mut self, rng: &mut Rng<E>) -> Result<(), Error> {
+        rng.try_fill_bytes(self)
+    }
+}
+
+// `try_fill` for integer slices: the bytes come from `try_fill_bytes` so engine errors
+// reach the caller, read little-endian they match `fill` with the default `fill_bytes`
+macro_rules! try_fill_from_bytes {
+    ($ty:ty) => {
+        fn try_fill<E: Engine>(&mut self, rng: &mut Rng<E>) -> Result<(), Error> {
+            const SIZE: usize = std::mem::size_of::<$ty>();
+            let mut buf = [0u8; 256];
+            for chunk in self.chunks_mut(buf.len() / SIZE) {
+                let bytes = &mut buf[..chunk.len() * SIZE];
+                rng.try_fill_bytes(bytes)?;
+                for (value, bytes) in chunk.iter_mut().zip(bytes.chunks_exact(SIZE)) {
+                    *value = <$ty>::from_le_bytes(bytes.try_into().unwrap());
+                }
+            }
+            Ok(())
+        }
+    };
+}
+
+impl Fill for [i8] {
+    fn fill<E: Engine>(&mut self, rng: &mut Rng<E>) {
+        for chunk in self.chunks_mut(8) {
+            let bytes = rng.next_u64().to_le_bytes();
+            for (value, byte) in chunk.iter_mut().zip(bytes.iter()) {
+                *value = *byte as i8;
+            }
+        }
+    }
+
+    try_fill_from_bytes!(i8);
+}
+
+// Splits each u64 into as many values as fit, lowest bits first
+macro_rules! fill_from_u64 {
+    ($($ty:ty),+) => {
+        $(
+            impl Fill for [$ty] {
+                fn fill<E: Engine>(&mut self, rng: &mut Rng<E>) {
+                    const BITS: usize = std::mem::size_of::<$ty>() * 8;
+                    for chunk in self.chunks_mut(64 / BITS) {
+                        let mut bits = rng.next_u64();
+                        for value in chunk.iter_mut() {
+                            *value = bits as $ty;
+                            bits = bits.checked_shr(BITS as u32).unwrap_or(0);
+                        }
+                    }
+                }
+
+                try_fill_from_bytes!($ty);
+            }
+        )+
+    };
+}
+
+fill_from_u64!(u16, u32, u64, usize, i16, i32, i64, isize);
+
+impl Fill for [u128] {
+    fn fill<E: Engine>(&mut self, rng: &mut Rng<E>) {
+        for value in self.iter_mut() {
+            *value = rng.next_u128();
+        }
+    }
+
+    try_fill_from_bytes!(u128);
+}
+
+impl Fill for [i128] {
+    fn fill<E: Engine>(&mut self, rng: &mut Rng<E>) {
+        for value in self.iter_mut() {
+            *value = rng.next_u128() as i128;
+        }
+    }
+
+    try_fill_from_bytes!(i128);
+}
+
+impl<T, const N: usize> Fill for [T; N]
+where
+    [T]: Fill,
+{
+    fn fill<E: Engine>(&mut self, rng: &mut Rng<E>) {
+        Fill::fill(&mut self[..], rng)
+    }
+
+    fn try_fill<E: Engine>(&mut self, rng: &mut Rng<E>) -> Result<(), Error> {
+        Fill::try_fill(&mut self[..], rng)
+    }
+}
+
+impl<E: Engine> Rng<E> {
+    pub fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.engine_mut().fill_bytes(dest)
+    }
+
+    pub fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.engine_mut().try_fill_bytes(dest)
+    }
+
+    /// Fills a slice or array of integers, drawing from the engine only as often as needed.
+    pub fn fill<T: Fill + ?Sized>(&mut self, dest: &mut T) {
+        Fill::fill(dest, self)
+    }
+
+    pub fn try_fill<T: Fill + ?Sized>(&mut self, dest: &mut T) -> Result<(), Error> {
+        Fill::try_fill(dest, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytes_use_every_bit() {
+        let mut a: Rng = Rng::with_seed(20);
+        let mut b: Rng = Rng::with_seed(20);
+
+        let mut buf = [0u8; 16];
+        a.fill(&mut buf);
+        let mut expected = b.next_u64().to_le_bytes().to_vec();
+        expected.extend_from_slice(&b.next_u64().to_le_bytes());
+        assert_eq!(&buf[..], &expected[..]);
+    }
+
+    #[test]
+    fn words_split_each_output() {
+        let mut a: Rng = Rng::with_seed(20);
+        let mut b: Rng = Rng::with_seed(20);
+
+        let mut words = [0u16; 5];
+        a.fill(&mut words);
+        let first = b.next_u64();
+        let second = b.next_u64();
+        assert_eq!(
+            words,
+            [
+                first as u16,
+                (first >> 16) as u16,
+                (first >> 32) as u16,
+                (first >> 48) as u16,
+                second as u16
+            ]
+        );
+
+        let mut words = [0u64; 3];
+        a.fill(&mut words);
+        assert_eq!(words, [b.next_u64(), b.next_u64(), b.next_u64()]);
+
+        let mut signed = [0i32; 2];
+        a.fill(&mut signed);
+        let value = b.next_u64();
+        assert_eq!(signed, [value as i32, (value >> 32) as i32]);
+    }
+
+    struct Failing;
+
+    impl Engine for Failing {
+        fn from_seed(_seed: u128) -> Failing {
+            Failing
+        }
+
+        fn next_u32(&mut self) -> u32 {
+            0
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            0
+        }
+
+        fn try_fill_bytes(&mut self, _dest: &mut [u8]) -> Result<(), Error> {
+            Err(Error::Unavailable)
+        }
+    }
+
+    #[test]
+    fn try_fill_matches_fill() {
+        let mut a: Rng = Rng::with_seed(20);
+        let mut b: Rng = Rng::with_seed(20);
+
+        let (mut x, mut y) = ([0u16; 300], [0u16; 300]);
+        a.fill(&mut x);
+        b.try_fill(&mut y).unwrap();
+        assert_eq!(&x[..], &y[..]);
+
+        let (mut x, mut y) = ([0i128; 3], [0i128; 3]);
+        a.fill(&mut x);
+        b.try_fill(&mut y).unwrap();
+        assert_eq!(x, y);
+    }
+
+    #[test]
+    fn try_fill_reports_engine_errors() {
+        let mut rng = Rng::from_engine(Failing);
+        assert_eq!(rng.try_fill(&mut [0u8; 4]), Err(Error::Unavailable));
+        assert_eq!(rng.try_fill(&mut [0u16; 4]), Err(Error::Unavailable));
+        assert_eq!(rng.try_fill(&mut [0i128; 4]), Err(Error::Unavailable));
+
+        let mut rng = Rng::new();
+        let mut buf = [0u8; 32];
+        assert_eq!(rng.try_fill(&mut buf), Ok(()));
+        assert_ne!(buf, [0; 32]);
+    }
+
+    #[test]
+    fn fills_whole_buffer() {
+        let mut rng = Rng::new();
+        let mut buf = vec![0u8; 4099];
+        rng.fill(&mut buf[..]);
+        let zeros = buf.iter().filter(|b| **b == 0).count();
+        assert!(zeros < 64, "{}", zeros);
+
+        let mut wide = [0i128; 4];
+        rng.fill(&mut wide);
+        assert!(wide.iter().all(|v| *v != 0));
+
+        let mut empty: [u32; 0] = [];
+        rng.fill(&mut empty);
+    }
+}
@@ -0,0 +1,139 @@
+use crate::base::Rng;
+use crate::engine::Engine;
+
+const F32_EPSILON: f32 = 1.0 / (1u32 << 23) as f32;
+const F64_EPSILON: f64 = 1.0 / (1u64 << 52) as f64;
+
+/// Which of the endpoints of the unit interval a float draw may hit.
+pub trait Interval {
+    fn f32_from_bits(bits: u32) -> f32;
+    fn f64_from_bits(bits: u64) -> f64;
+}
+
+/// [0, 1), the interval `rand::<f32>()` and `rand::<f64>()` draw from.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Standard;
+
+/// (0, 1), never 0 nor 1.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Open01;
+
+/// (0, 1], never 0, safe to take the log of.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct OpenClosed01;
+
+/// [0, 1], both endpoints included.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Closed01;
+
+impl Interval for Standard {
+    fn f32_from_bits(bits: u32) -> f32 {
+        (bits >> 8) as f32 * (F32_EPSILON / 2.0)
+    }
+
+    fn f64_from_bits(bits: u64) -> f64 {
+        (bits >> 11) as f64 * (F64_EPSILON / 2.0)
+    }
+}
+
+impl Interval for Open01 {
+    // The midpoints of the Standard grid, shifted away from 0 by half a step
+    fn f32_from_bits(bits: u32) -> f32 {
+        ((bits >> 9) as f32 + 0.5) * F32_EPSILON
+    }
+
+    fn f64_from_bits(bits: u64) -> f64 {
+        ((bits >> 12) as f64 + 0.5) * F64_EPSILON
+    }
+}
+
+impl Interval for OpenClosed01 {
+    fn f32_from_bits(bits: u32) -> f32 {
+        ((bits >> 8) + 1) as f32 * (F32_EPSILON / 2.0)
+    }
+
+    fn f64_from_bits(bits: u64) -> f64 {
+        ((bits >> 11) + 1) as f64 * (F64_EPSILON / 2.0)
+    }
+}
+
+impl Interval for Closed01 {
+    // 2^24 and 2^53 evenly spaced points, the last one being 1
+    fn f32_from_bits(bits: u32) -> f32 {
+        (bits >> 8) as f32 / ((1u32 << 24) - 1) as f32
+    }
+
+    fn f64_from_bits(bits: u64) -> f64 {
+        (bits >> 11) as f64 / ((1u64 << 53) - 1) as f64
+    }
+}
+
+/// Float types that can be drawn from an `Interval`.
+pub trait UnitFloat: Sized {
+    fn rand_unit<I: Interval, E: Engine>(rng: &mut Rng<E>) -> Self;
+}
+
+impl UnitFloat for f32 {
+    fn rand_unit<I: Interval, E: Engine>(rng: &mut Rng<E>) -> f32 {
+        I::f32_from_bits(rng.next_u32())
+    }
+}
+
+impl UnitFloat for f64 {
+    fn rand_unit<I: Interval, E: Engine>(rng: &mut Rng<E>) -> f64 {
+        I::f64_from_bits(rng.next_u64())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn standard_endpoints() {
+        assert_eq!(Standard::f32_from_bits(0), 0.0);
+        assert!(Standard::f32_from_bits(u32::MAX) < 1.0);
+        assert_eq!(Standard::f64_from_bits(0), 0.0);
+        assert!(Standard::f64_from_bits(u64::MAX) < 1.0);
+    }
+
+    #[test]
+    fn open_endpoints() {
+        assert!(Open01::f32_from_bits(0) > 0.0);
+        assert!(Open01::f32_from_bits(u32::MAX) < 1.0);
+        assert!(Open01::f64_from_bits(0) > 0.0);
+        assert!(Open01::f64_from_bits(u64::MAX) < 1.0);
+    }
+
+    #[test]
+    fn open_closed_endpoints() {
+        assert!(OpenClosed01::f32_from_bits(0) > 0.0);
+        assert_eq!(OpenClosed01::f32_from_bits(u32::MAX), 1.0);
+        assert!(OpenClosed01::f64_from_bits(0) > 0.0);
+        assert_eq!(OpenClosed01::f64_from_bits(u64::MAX), 1.0);
+    }
+
+    #[test]
+    fn closed_endpoints() {
+        assert_eq!(Closed01::f32_from_bits(0), 0.0);
+        assert_eq!(Closed01::f32_from_bits(u32::MAX), 1.0);
+        assert_eq!(Closed01::f64_from_bits(0), 0.0);
+        assert_eq!(Closed01::f64_from_bits(u64::MAX), 1.0);
+    }
+
+    #[test]
+    fn unit_mean() {
+        let mut rng: Rng = Rng::with_seed(3);
+        let mean = (0..100_000)
+            .map(|_| f64::rand_unit::<Open01, _>(&mut rng))
+            .sum::<f64>()
+            / 100_000.0;
+        assert!((mean - 0.5).abs() < 0.005, "{}", mean);
+
+        let mean = (0..100_000)
+            .map(|_| f32::rand_unit::<Closed01, _>(&mut rng) as f64)
+            .sum::<f64>()
+            / 100_000.0;
+        assert!((mean - 0.5).abs() < 0.005, "{}", mean);
+    }
+}
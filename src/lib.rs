@@ -1,349 +1,325 @@
 mod base;
+#[cfg(feature = "chacha")]
+mod chacha;
+mod composite;
+pub mod distributions;
+mod engine;
+mod entropy;
+mod error;
+mod fill;
+mod interval;
+mod pcg;
+mod seed;
+mod xoshiro;
+
+pub use base::{Randomable, RandomableRange, Rng};
+#[cfg(feature = "chacha")]
+pub use chacha::{ChaCha12, ChaCha20, ChaCha8};
+pub use engine::{Engine, Lcg};
+pub use error::Error;
+pub use fill::Fill;
+pub use interval::{Closed01, Interval, Open01, OpenClosed01, Standard, UnitFloat};
+pub use pcg::{Pcg32, Pcg64};
+pub use seed::{expand_seed, SplitMix64, SEED_VERSION};
+#[cfg(feature = "derive")]
+pub use simplerand_derive::Randomable;
+pub use xoshiro::{Xoroshiro128PlusPlus, Xoshiro256Plus, Xoshiro256StarStar};
 
 #[macro_use]
 extern crate lazy_static;
 
+// Lets the code generated by the derive refer to `::simplerand` from inside this crate too
+#[cfg(feature = "derive")]
+extern crate self as simplerand;
+
+use distributions::Distribution;
+use std::cell::RefCell;
+use std::ops::{Bound, RangeBounds};
+#[cfg(feature = "atomic")]
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex;
 
 pub fn rand<T: Randomable>() -> T {
-    T::rand()
+    with_global(|rng| T::rand(rng))
 }
 
-pub fn randn<T: Randomable>(n: T) -> T {
-    T::randn(n)
+pub fn randn<T: RandomableRange>(n: T) -> T {
+    with_global(|rng| T::randn(rng, n))
 }
 
-pub fn rand_range<T: Randomable>(min: T, max: T) -> T {
-    T::rand_range(min, max)
+pub fn rand_range<T: RandomableRange>(min: T, max: T) -> T {
+    with_global(|rng| T::rand_range(rng, min, max))
 }
 
-pub fn set_seed<T: Randomable>(s: u128) {
-    T::set_seed(s)
+/// `randn` that returns an error instead of panicking on an empty or negative bound.
+pub fn try_randn<T: RandomableRange>(n: T) -> Result<T, Error> {
+    try_with_global(|rng| T::try_randn(rng, n))?
 }
 
-#[derive(Debug, Copy, Clone)]
-pub struct Rng {
-    base: base::Rng,
+pub fn try_rand_range<T: RandomableRange>(min: T, max: T) -> Result<T, Error> {
+    try_with_global(|rng| T::try_rand_range(rng, min, max))?
 }
 
-impl Default for Rng {
-    fn default() -> Self {
-        Self::new()
-    }
+/// Draws from `a..b`, `a..=b`, `..b`, `..=b`, `a..`, `..` or a `(Bound, Bound)` pair.
+pub fn gen_range<T: RandomableRange, R: RangeBounds<T>>(range: R) -> T {
+    let bounds = owned_bounds(&range);
+    with_global(|rng| T::gen_range(rng, bounds))
 }
 
-impl Rng {
-    pub fn new() -> Rng {
-        Rng {
-            base: base::Rng::new(),
-        }
-    }
+pub fn try_gen_range<T: RandomableRange, R: RangeBounds<T>>(range: R) -> Result<T, Error> {
+    let bounds = owned_bounds(&range);
+    try_with_global(|rng| T::try_gen_range(rng, bounds))?
+}
 
-    pub fn set_seed(&mut self, s: u128) {
-        self.base.set_seed(s)
-    }
+/// `None` with probability `none_probability`, otherwise a random value.
+pub fn rand_option<T: Randomable>(none_probability: f64) -> Option<T> {
+    with_global(|rng| rng.rand_option(none_probability))
+}
 
-    pub fn get_seed(self) -> u128 {
-        self.base.get_seed()
-    }
+pub fn rand_vec<T: Randomable>(len: usize) -> Vec<T> {
+    with_global(|rng| rng.rand_vec(len))
+}
 
-    pub fn rand(&mut self) -> u128 {
-        self.base.rand()
-    }
+/// Fills `dest` from the global generator, taking the shared lock once for the whole buffer.
+pub fn fill_bytes(dest: &mut [u8]) {
+    with_global(|rng| rng.fill_bytes(dest))
+}
 
-    pub fn randn(&mut self, n: u128) -> u128 {
-        self.base.randn(n)
-    }
+pub fn fill<T: Fill + ?Sized>(dest: &mut T) {
+    with_global(|rng| rng.fill(dest))
+}
 
-    pub fn rand_range(&mut self, min: u128, max: u128) -> u128 {
-        self.base.rand_range(min, max)
-    }
+pub fn try_fill<T: Fill + ?Sized>(dest: &mut T) -> Result<(), Error> {
+    try_with_global(|rng| rng.try_fill(dest))?
 }
 
+/// `true` with probability `p`.
+pub fn gen_bool(p: f64) -> bool {
+    with_global(|rng| rng.gen_bool(p))
+}
+
+pub fn try_gen_bool(p: f64) -> Result<bool, Error> {
+    try_with_global(|rng| rng.try_gen_bool(p))?
+}
+
+/// A value drawn from `distribution`, see the `distributions` module.
+pub fn sample<T, D: Distribution<T>>(distribution: &D) -> T {
+    with_global(|rng| rng.sample(distribution))
+}
+
+/// A float in the unit interval selected by `interval`, e.g. `rand_unit::<f64, _>(Open01)`.
+pub fn rand_unit<T: UnitFloat, I: Interval>(_interval: I) -> T {
+    with_global(|rng| T::rand_unit::<I, _>(rng))
+}
+
+/// Reseeds the global generator, only the calling thread's one in `GlobalMode::ThreadLocal`.
+pub fn set_seed<T: Randomable>(s: u128) {
+    with_global(|rng| rng.set_seed(s))
+}
+
+#[cfg(not(feature = "atomic"))]
 lazy_static! {
     static ref BASE_RAND: Mutex<Rng> = Mutex::new(Rng::new());
 }
 
-pub struct Random {
-    rng: Mutex<base::Rng>,
-}
+// The LCG state always fits in 63 bits, so the shared generator can live in one atomic word.
+// This only holds for `Lcg`: the assertion stops the build if the global engine changes.
+#[cfg(feature = "atomic")]
+const _: fn(Rng) -> Rng<Lcg> = |rng| rng;
 
-impl Random {
-    pub fn new(s: u128) -> Random {
-        let mut rng = base::Rng::new();
-        rng.set_seed(s);
-        Random {
-            rng: Mutex::new(rng),
-        }
-    }
+#[cfg(feature = "atomic")]
+lazy_static! {
+    static ref BASE_STATE: AtomicU64 = AtomicU64::new(Rng::new().engine().state() as u64);
+}
 
-    pub fn rand<T: base::Randomable>(&self) -> T {
-        let mut rng = self.rng.lock().unwrap();
-        T::rand(&mut rng)
-    }
+thread_local! {
+    static THREAD_RAND: RefCell<Rng> = RefCell::new(Rng::new());
+}
 
-    pub fn randn<T: base::Randomable>(&self, n: T) -> T {
-        let mut rng = self.rng.lock().unwrap();
-        T::randn(&mut rng, n)
-    }
+static SHARED_MODE: AtomicBool = AtomicBool::new(false);
+
+/// Where the free functions take their numbers from.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GlobalMode {
+    /// Every thread owns an independently seeded generator, the default.
+    ThreadLocal,
+    /// All threads share one generator, so a single `set_seed` gives one
+    /// deterministic sequence. It sits behind a mutex, or in an atomic word
+    /// updated with compare-and-swap when the `atomic` feature is enabled.
+    ///
+    /// With `atomic` the draw is recomputed from the new state whenever another
+    /// thread advanced the generator first, so under contention the work of a
+    /// call (e.g. the closure filling a buffer) can run several times. The state
+    /// must fit in a `u64`, which ties the feature to the `Lcg` engine.
+    Shared,
+}
 
-    pub fn rand_range<T: base::Randomable>(&self, min: T, max: T) -> T {
-        let mut rng = self.rng.lock().unwrap();
-        T::rand_range(&mut rng, min, max)
-    }
+pub fn set_global_mode(mode: GlobalMode) {
+    SHARED_MODE.store(mode == GlobalMode::Shared, Ordering::SeqCst);
+}
 
-    pub fn set_seed(&self, s: u128) {
-        self.rng.lock().unwrap().set_seed(s);
+pub fn global_mode() -> GlobalMode {
+    if SHARED_MODE.load(Ordering::SeqCst) {
+        GlobalMode::Shared
+    } else {
+        GlobalMode::ThreadLocal
     }
 }
 
-pub trait Randomable: Sized {
-    fn rand() -> Self;
-    fn randn(n: Self) -> Self;
-    fn rand_range(min: Self, max: Self) -> Self;
+// Owned bounds are Copy, so the closures passed to with_global can be rerun
+fn owned_bounds<T: Copy>(range: &impl RangeBounds<T>) -> (Bound<T>, Bound<T>) {
+    (range.start_bound().cloned(), range.end_bound().cloned())
+}
 
-    fn set_seed(s: u128) {
-        BASE_RAND.lock().unwrap().set_seed(s);
-    }
+// With the atomic feature `f` is retried when another thread won the race,
+// so it must not have side effects besides drawing from the generator.
+fn with_global<T>(f: impl FnMut(&mut Rng) -> T) -> T {
+    with_mode(global_mode(), f)
 }
 
-impl Randomable for u8 {
-    fn rand() -> u8 {
-        let range: u128 = (1 << 8) - 1;
-        BASE_RAND.lock().unwrap().rand_range(0, range) as u8
-    }
-    fn randn(n: u8) -> u8 {
-        BASE_RAND.lock().unwrap().randn(n as u128) as u8
-    }
-    fn rand_range(min: u8, max: u8) -> u8 {
-        BASE_RAND
-            .lock()
-            .unwrap()
-            .rand_range(min as u128, max as u128) as u8
-    }
+fn try_with_global<T>(f: impl FnMut(&mut Rng) -> T) -> Result<T, Error> {
+    try_with_mode(global_mode(), f)
 }
 
-impl Randomable for u16 {
-    fn rand() -> u16 {
-        let range: u128 = (1 << 16) - 1;
-        BASE_RAND.lock().unwrap().rand_range(0, range) as u16
-    }
-    fn randn(n: u16) -> u16 {
-        BASE_RAND.lock().unwrap().randn(n as u128) as u16
-    }
-    fn rand_range(min: u16, max: u16) -> u16 {
-        BASE_RAND
-            .lock()
-            .unwrap()
-            .rand_range(min as u128, max as u128) as u16
-    }
+fn with_mode<T>(mode: GlobalMode, f: impl FnMut(&mut Rng) -> T) -> T {
+    try_with_mode(mode, f).unwrap()
 }
 
-impl Randomable for u32 {
-    fn rand() -> u32 {
-        let range: u128 = (1 << 32) - 1;
-        BASE_RAND.lock().unwrap().rand_range(0, range) as u32
-    }
-    fn randn(n: u32) -> u32 {
-        BASE_RAND.lock().unwrap().randn(n as u128) as u32
-    }
-    fn rand_range(min: u32, max: u32) -> u32 {
-        BASE_RAND
-            .lock()
-            .unwrap()
-            .rand_range(min as u128, max as u128) as u32
+fn try_with_mode<T>(mode: GlobalMode, mut f: impl FnMut(&mut Rng) -> T) -> Result<T, Error> {
+    match mode {
+        GlobalMode::ThreadLocal => Ok(THREAD_RAND.with(|rng| f(&mut rng.borrow_mut()))),
+        GlobalMode::Shared => with_shared(f),
     }
 }
 
-impl Randomable for u64 {
-    fn rand() -> u64 {
-        let range: u128 = (1 << 63) - 1;
-        BASE_RAND.lock().unwrap().rand_range(0, range) as u64
-    }
-    fn randn(n: u64) -> u64 {
-        BASE_RAND.lock().unwrap().randn(n as u128) as u64
-    }
-    fn rand_range(min: u64, max: u64) -> u64 {
-        BASE_RAND
-            .lock()
-            .unwrap()
-            .rand_range(min as u128, max as u128) as u64
-    }
+#[cfg(not(feature = "atomic"))]
+fn with_shared<T>(mut f: impl FnMut(&mut Rng) -> T) -> Result<T, Error> {
+    let mut rng = BASE_RAND.lock().map_err(|_| Error::Poisoned)?;
+    Ok(f(&mut rng))
 }
 
-impl Randomable for u128 {
-    fn rand() -> u128 {
-        let range: u128 = (1 << 127) - 1;
-        BASE_RAND.lock().unwrap().rand_range(0, range)
-    }
-    fn randn(n: u128) -> u128 {
-        BASE_RAND.lock().unwrap().randn(n)
-    }
-    fn rand_range(min: u128, max: u128) -> u128 {
-        BASE_RAND.lock().unwrap().rand_range(min, max)
-    }
+#[cfg(feature = "atomic")]
+fn with_shared<T>(mut f: impl FnMut(&mut Rng) -> T) -> Result<T, Error> {
+    let mut result = None;
+    let _ = BASE_STATE.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |state| {
+        let mut rng = Rng::from_engine(Lcg::from_state(state as u128));
+        result = Some(f(&mut rng));
+        Some(rng.engine().state() as u64)
+    });
+    Ok(result.unwrap())
 }
 
-impl Randomable for usize {
-    fn rand() -> usize {
-        let range: u128 = (1 << 32) - 1;
-        BASE_RAND.lock().unwrap().rand_range(0, range) as usize
-    }
-    fn randn(n: usize) -> usize {
-        BASE_RAND.lock().unwrap().randn(n as u128) as usize
-    }
-    fn rand_range(min: usize, max: usize) -> usize {
-        BASE_RAND
-            .lock()
-            .unwrap()
-            .rand_range(min as u128, max as u128) as usize
-    }
+pub struct Random<E = Lcg> {
+    rng: Mutex<Rng<E>>,
 }
 
-impl Randomable for i8 {
-    fn rand() -> i8 {
-        let range: u128 = (1 << 7) - 1;
-        BASE_RAND.lock().unwrap().rand_range(0, range) as i8
-    }
-    fn randn(n: i8) -> i8 {
-        BASE_RAND.lock().unwrap().randn(n as u128) as i8
-    }
-    fn rand_range(min: i8, max: i8) -> i8 {
-        BASE_RAND
-            .lock()
-            .unwrap()
-            .rand_range(0u128, (max - min) as u128) as i8
-            + min
+impl Random {
+    pub fn new(s: u128) -> Random {
+        Random::with_seed(s)
     }
 }
 
-impl Randomable for i16 {
-    fn rand() -> i16 {
-        let range: u128 = (1 << 15) - 1;
-        BASE_RAND.lock().unwrap().rand_range(0, range) as i16
+impl<E: Engine> Random<E> {
+    pub fn with_seed(s: u128) -> Random<E> {
+        Random {
+            rng: Mutex::new(Rng::with_seed(s)),
+        }
     }
-    fn randn(n: i16) -> i16 {
-        BASE_RAND.lock().unwrap().randn(n as u128) as i16
+
+    pub fn from_entropy() -> Random<E> {
+        Random {
+            rng: Mutex::new(Rng::from_entropy()),
+        }
     }
-    fn rand_range(min: i16, max: i16) -> i16 {
-        BASE_RAND
-            .lock()
-            .unwrap()
-            .rand_range(0u128, (max - min) as u128) as i16
-            + min
+
+    pub fn from_engine(engine: E) -> Random<E> {
+        Random {
+            rng: Mutex::new(Rng::from_engine(engine)),
+        }
     }
-}
 
-impl Randomable for i32 {
-    fn rand() -> i32 {
-        let range: u128 = (1 << 31) - 1;
-        BASE_RAND.lock().unwrap().rand_range(0, range) as i32
+    pub fn rand<T: Randomable>(&self) -> T {
+        let mut rng = self.rng.lock().unwrap();
+        T::rand(&mut rng)
     }
-    fn randn(n: i32) -> i32 {
-        BASE_RAND.lock().unwrap().randn(n as u128) as i32
+
+    pub fn randn<T: RandomableRange>(&self, n: T) -> T {
+        let mut rng = self.rng.lock().unwrap();
+        T::randn(&mut rng, n)
     }
-    fn rand_range(min: i32, max: i32) -> i32 {
-        BASE_RAND
-            .lock()
-            .unwrap()
-            .rand_range(0u128, (max - min) as u128) as i32
-            + min
+
+    pub fn rand_range<T: RandomableRange>(&self, min: T, max: T) -> T {
+        let mut rng = self.rng.lock().unwrap();
+        T::rand_range(&mut rng, min, max)
     }
-}
 
-impl Randomable for i64 {
-    fn rand() -> i64 {
-        let range: u128 = (1 << 63) - 1;
-        BASE_RAND.lock().unwrap().rand_range(0, range) as i64
+    pub fn try_randn<T: RandomableRange>(&self, n: T) -> Result<T, Error> {
+        let mut rng = self.rng.lock().map_err(|_| Error::Poisoned)?;
+        T::try_randn(&mut rng, n)
     }
-    fn randn(n: i64) -> i64 {
-        BASE_RAND.lock().unwrap().randn(n as u128) as i64
+
+    pub fn try_rand_range<T: RandomableRange>(&self, min: T, max: T) -> Result<T, Error> {
+        let mut rng = self.rng.lock().map_err(|_| Error::Poisoned)?;
+        T::try_rand_range(&mut rng, min, max)
     }
-    fn rand_range(min: i64, max: i64) -> i64 {
-        BASE_RAND
-            .lock()
-            .unwrap()
-            .rand_range(0u128, (max - min) as u128) as i64
-            + min
+
+    pub fn gen_range<T: RandomableRange, R: RangeBounds<T>>(&self, range: R) -> T {
+        let mut rng = self.rng.lock().unwrap();
+        T::gen_range(&mut rng, range)
     }
-}
 
-impl Randomable for i128 {
-    fn rand() -> i128 {
-        let range: u128 = (1 << 127) - 1;
-        BASE_RAND.lock().unwrap().rand_range(0, range) as i128
+    pub fn try_gen_range<T: RandomableRange, R: RangeBounds<T>>(
+        &self,
+        range: R,
+    ) -> Result<T, Error> {
+        let mut rng = self.rng.lock().map_err(|_| Error::Poisoned)?;
+        T::try_gen_range(&mut rng, range)
     }
-    fn randn(n: i128) -> i128 {
-        BASE_RAND.lock().unwrap().randn(n as u128) as i128
+
+    pub fn gen_bool(&self, p: f64) -> bool {
+        self.rng.lock().unwrap().gen_bool(p)
     }
-    fn rand_range(min: i128, max: i128) -> i128 {
-        BASE_RAND
+
+    pub fn try_gen_bool(&self, p: f64) -> Result<bool, Error> {
+        self.rng
             .lock()
-            .unwrap()
-            .rand_range(min as u128, max as u128) as i128
+            .map_err(|_| Error::Poisoned)?
+            .try_gen_bool(p)
     }
-}
 
-impl Randomable for isize {
-    fn rand() -> isize {
-        let range: u128 = (1 << 31) - 1;
-        BASE_RAND.lock().unwrap().rand_range(0, range) as isize
+    pub fn sample<T, D: Distribution<T>>(&self, distribution: &D) -> T {
+        self.rng.lock().unwrap().sample(distribution)
     }
-    fn randn(n: isize) -> isize {
-        BASE_RAND.lock().unwrap().randn(n as u128) as isize
-    }
-    fn rand_range(min: isize, max: isize) -> isize {
-        BASE_RAND
-            .lock()
-            .unwrap()
-            .rand_range(0u128, (max - min) as u128) as isize
-            + min
+
+    pub fn rand_option<T: Randomable>(&self, none_probability: f64) -> Option<T> {
+        self.rng.lock().unwrap().rand_option(none_probability)
     }
-}
 
-impl Randomable for f32 {
-    fn rand() -> f32 {
-        let range: u128 = (1 << 31) - 1;
-        let num = BASE_RAND.lock().unwrap().rand_range(10000, range);
-        let divider = BASE_RAND.lock().unwrap().rand_range(2, 9);
-        num as f32 / divider as f32
+    pub fn rand_vec<T: Randomable>(&self, len: usize) -> Vec<T> {
+        self.rng.lock().unwrap().rand_vec(len)
     }
-    fn randn(n: f32) -> f32 {
-        let divider = 29u128;
-        let range = n as u128 * divider;
 
-        let num = BASE_RAND.lock().unwrap().randn(range);
-        num as f32 / divider as f32
+    pub fn fill_bytes(&self, dest: &mut [u8]) {
+        self.rng.lock().unwrap().fill_bytes(dest)
     }
-    fn rand_range(min: f32, max: f32) -> f32 {
-        let num = BASE_RAND
-            .lock()
-            .unwrap()
-            .rand_range(0u128, (max - min) as u128);
-        (num as f32 + min) / 1.164_534_3
+
+    pub fn fill<T: Fill + ?Sized>(&self, dest: &mut T) {
+        self.rng.lock().unwrap().fill(dest)
     }
-}
 
-impl Randomable for f64 {
-    fn rand() -> f64 {
-        let range: u128 = (1 << 63) - 1;
-        let num = BASE_RAND.lock().unwrap().rand_range(10000, range);
-        let divider = BASE_RAND.lock().unwrap().rand_range(2, 9);
-        num as f64 / divider as f64
+    pub fn try_fill<T: Fill + ?Sized>(&self, dest: &mut T) -> Result<(), Error> {
+        let mut rng = self.rng.lock().map_err(|_| Error::Poisoned)?;
+        rng.try_fill(dest)
     }
-    fn randn(n: f64) -> f64 {
-        let divider = 29u128;
-        let range = n as u128 * divider;
 
-        let num = BASE_RAND.lock().unwrap().randn(range);
-        num as f64 / divider as f64
+    pub fn rand_unit<T: UnitFloat, I: Interval>(&self, _interval: I) -> T {
+        let mut rng = self.rng.lock().unwrap();
+        T::rand_unit::<I, _>(&mut rng)
     }
-    fn rand_range(min: f64, max: f64) -> f64 {
-        let num = BASE_RAND
-            .lock()
-            .unwrap()
-            .rand_range(0u128, (max - min) as u128);
-        (num as f64 + min) / 1.16453434
+
+    pub fn set_seed(&self, s: u128) {
+        self.rng.lock().unwrap().set_seed(s);
     }
 }
 
@@ -352,8 +328,12 @@ mod tests {
     use crate::*;
     use std::time::Instant;
 
+    // The global generator is seeded from entropy, pin it where two draws must differ
+    const SEED: u128 = 1;
+
     #[test]
     fn base_rand() {
+        set_seed::<u64>(SEED);
         let n1 = rand::<u32>();
         let n2 = rand::<u32>();
 
@@ -364,6 +344,7 @@ mod tests {
 
     #[test]
     fn base_randn() {
+        set_seed::<u64>(SEED);
         let n1 = randn::<u32>(10000);
         let n2 = randn::<u32>(10000);
 
@@ -380,22 +361,24 @@ mod tests {
 
     #[test]
     fn base_rand_range() {
+        set_seed::<u64>(SEED);
         let n1 = rand_range::<u32>(4000, 10000);
         let n2 = rand_range::<u32>(4000, 10000);
 
         if n1 == n2 {
             panic!("{} shouldn't be equal with {}", n1, n2)
         }
-        if n1 > 10000 && n1 < 4000 {
+        if !(4000..=10000).contains(&n1) {
             panic!("{} should be between 4000 and 10000", n1)
         }
-        if n2 > 10000 && n2 < 4000 {
+        if !(4000..=10000).contains(&n2) {
             panic!("{} should be between 4000 and 10000", n2)
         }
     }
 
     #[test]
     fn random() {
+        set_seed::<u64>(SEED);
         let mut prev_data: i64 = 0;
         for _ in 1..10 {
             let data = randn(10000);
@@ -416,6 +399,7 @@ mod tests {
 
     #[test]
     fn random_u8() {
+        set_seed::<u64>(SEED);
         let min = 10;
         let max = 30;
         let n1 = rand_range::<u8>(min, max);
@@ -434,6 +418,7 @@ mod tests {
 
     #[test]
     fn random_u16() {
+        set_seed::<u64>(SEED);
         let min = 10;
         let max = 30000;
         let n1 = rand_range::<u16>(min, max);
@@ -452,6 +437,7 @@ mod tests {
 
     #[test]
     fn random_u32() {
+        set_seed::<u64>(SEED);
         let min = 10;
         let max = 3000000000;
         let n1 = rand_range::<u32>(min, max);
@@ -470,6 +456,7 @@ mod tests {
 
     #[test]
     fn random_u64() {
+        set_seed::<u64>(SEED);
         let min = 10;
         let max = 3000000000000000000;
         let n1 = rand_range::<u64>(min, max);
@@ -488,6 +475,7 @@ mod tests {
 
     #[test]
     fn random_usize() {
+        set_seed::<u64>(SEED);
         let min = 10;
         let max = 304444;
         let n1 = rand_range::<usize>(min, max);
@@ -506,6 +494,7 @@ mod tests {
 
     #[test]
     fn random_i8() {
+        set_seed::<u64>(SEED);
         let min = 10;
         let max = 30;
         let n1 = rand_range::<i8>(min, max);
@@ -523,6 +512,7 @@ mod tests {
     }
     #[test]
     fn random_i16() {
+        set_seed::<u64>(SEED);
         let min = 10;
         let max = 30000;
         let n1 = rand_range::<i16>(min, max);
@@ -541,6 +531,7 @@ mod tests {
 
     #[test]
     fn random_i32() {
+        set_seed::<u64>(SEED);
         let min = 10;
         let max = 300000000;
         let n1 = rand_range::<i32>(min, max);
@@ -559,6 +550,7 @@ mod tests {
 
     #[test]
     fn random_i64() {
+        set_seed::<u64>(SEED);
         let min = 10;
         let max = 3000000000000000000;
         let n1 = rand_range::<i64>(min, max);
@@ -577,6 +569,7 @@ mod tests {
 
     #[test]
     fn random_isize() {
+        set_seed::<u64>(SEED);
         let min = 10;
         let max = 304444;
         let n1 = rand_range::<isize>(min, max);
@@ -595,6 +588,7 @@ mod tests {
 
     #[test]
     fn random_f32() {
+        set_seed::<u64>(SEED);
         let min = 10.1;
         let max = 30000.1;
         let n1 = rand_range::<f32>(min, max);
@@ -613,6 +607,7 @@ mod tests {
 
     #[test]
     fn random_f64() {
+        set_seed::<u64>(SEED);
         let min = 10.1;
         let max = 30000000000.1;
         let n1 = rand_range::<f64>(min, max);
@@ -631,6 +626,7 @@ mod tests {
 
     #[test]
     fn random_i8_negative_min() {
+        set_seed::<u64>(SEED);
         let min = -6;
         let max = 30;
         let n1 = rand_range::<i8>(min, max);
@@ -649,6 +645,7 @@ mod tests {
 
     #[test]
     fn random_i16_negative_min() {
+        set_seed::<u64>(SEED);
         let min = -6;
         let max = 30;
         let n1 = rand_range::<i16>(min, max);
@@ -667,6 +664,7 @@ mod tests {
 
     #[test]
     fn random_i32_negative_min() {
+        set_seed::<u64>(SEED);
         let min = -6;
         let max = 30;
         let n1 = rand_range::<i32>(min, max);
@@ -685,6 +683,7 @@ mod tests {
 
     #[test]
     fn random_i64_negative_min() {
+        set_seed::<u64>(SEED);
         let min = -6;
         let max = 30;
         let n1 = rand_range::<i64>(min, max);
@@ -703,6 +702,7 @@ mod tests {
 
     #[test]
     fn random_isize_negative_min() {
+        set_seed::<u64>(SEED);
         let min = -6;
         let max = 30;
         let n1 = rand_range::<isize>(min, max);
@@ -721,6 +721,7 @@ mod tests {
 
     #[test]
     fn random_i8_negative_range() {
+        set_seed::<u64>(SEED);
         let min = -30;
         let max = -6;
         let n1 = rand_range::<i8>(min, max);
@@ -739,6 +740,7 @@ mod tests {
 
     #[test]
     fn random_i16_negative_range() {
+        set_seed::<u64>(SEED);
         let min = -30;
         let max = -6;
         let n1 = rand_range::<i16>(min, max);
@@ -757,6 +759,7 @@ mod tests {
 
     #[test]
     fn random_i32_negative_range() {
+        set_seed::<u64>(SEED);
         let min = -30;
         let max = -6;
         let n1 = rand_range::<i32>(min, max);
@@ -775,6 +778,7 @@ mod tests {
 
     #[test]
     fn random_i64_negative_range() {
+        set_seed::<u64>(SEED);
         let min = -30;
         let max = -6;
         let n1 = rand_range::<i64>(min, max);
@@ -793,6 +797,7 @@ mod tests {
 
     #[test]
     fn random_isize_negative_range() {
+        set_seed::<u64>(SEED);
         let min = -30;
         let max = -6;
         let n1 = rand_range::<isize>(min, max);
@@ -811,6 +816,7 @@ mod tests {
 
     #[test]
     fn random_f32_negative_min() {
+        set_seed::<u64>(SEED);
         let min = -10.1;
         let max = 30000.1;
         let n1 = rand_range::<f32>(min, max);
@@ -829,6 +835,7 @@ mod tests {
 
     #[test]
     fn random_f64_negative_min() {
+        set_seed::<u64>(SEED);
         let min = -10.1;
         let max = 30000.1;
         let n1 = rand_range::<f64>(min, max);
@@ -847,6 +854,7 @@ mod tests {
 
     #[test]
     fn random_f32_negative_range() {
+        set_seed::<u64>(SEED);
         let min = -30000.1;
         let max = -10.1;
         let n1 = rand_range::<f32>(min, max);
@@ -865,6 +873,7 @@ mod tests {
 
     #[test]
     fn random_f64_negative_range() {
+        set_seed::<u64>(SEED);
         let min = -30000.1;
         let max = -10.1;
         let n1 = rand_range::<f64>(min, max);
@@ -881,10 +890,464 @@ mod tests {
         }
     }
 
+    #[test]
+    fn random_float_unit() {
+        for _ in 0..1000 {
+            let n = rand::<f32>();
+            assert!((0.0..1.0).contains(&n), "{}", n);
+            let n = rand::<f64>();
+            assert!((0.0..1.0).contains(&n), "{}", n);
+            let n = randn::<f64>(0.5);
+            assert!((0.0..0.5).contains(&n), "{}", n);
+        }
+    }
+
+    #[test]
+    fn random_float_sub_one_range() {
+        let random = Random::new(9);
+        for _ in 0..1000 {
+            let n = rand_range::<f32>(0.2, 0.3);
+            assert!((0.2..0.3).contains(&n), "{}", n);
+            let n = rand_range::<f64>(-0.3, -0.2);
+            assert!((-0.3..-0.2).contains(&n), "{}", n);
+            let n: f64 = random.rand_range(-1e300, 1e300);
+            assert!((-1e300..1e300).contains(&n), "{}", n);
+        }
+    }
+
+    #[test]
+    fn random_unit_intervals() {
+        let random = Random::new(4);
+        let mut rng: Rng = Rng::with_seed(4);
+        for _ in 0..1000 {
+            let n: f64 = rand_unit(Open01);
+            assert!(n > 0.0 && n < 1.0, "{}", n);
+            let n: f32 = rand_unit(OpenClosed01);
+            assert!(n > 0.0 && n <= 1.0, "{}", n);
+            let n: f64 = random.rand_unit(Closed01);
+            assert!((0.0..=1.0).contains(&n), "{}", n);
+            let n: f32 = random.rand_unit(Standard);
+            assert!((0.0..1.0).contains(&n), "{}", n);
+            let n: f64 = rng.rand_unit(OpenClosed01);
+            assert!(n.ln().is_finite(), "{}", n);
+        }
+
+        // Standard is what rand() draws from
+        let random = Random::new(4);
+        let a: f64 = random.rand_unit(Standard);
+        let b: f64 = Random::new(4).rand();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn random_gen_range() {
+        let random = Random::new(2);
+        let mut rng: Rng = Rng::with_seed(2);
+        for _ in 0..1000 {
+            let n = gen_range(10u32..=20);
+            assert!((10..=20).contains(&n), "{}", n);
+            let n = gen_range::<i8, _>(..=-120);
+            assert!(n <= -120, "{}", n);
+            let n = gen_range(0.25f64..0.5);
+            assert!((0.25..0.5).contains(&n), "{}", n);
+            let n: u16 = random.gen_range(65530..);
+            assert!(n >= 65530, "{}", n);
+            let n: i64 = rng.gen_range(-5..5);
+            assert!((-5..5).contains(&n), "{}", n);
+        }
+
+        let n: u64 = gen_range(..);
+        let m: u64 = gen_range(0..=u64::MAX);
+        assert_ne!(n, m);
+        assert_eq!(rng.rand_range_inclusive(0, 0), 0);
+    }
+
+    #[test]
+    fn random_try() {
+        assert_eq!(try_randn::<u32>(0), Err(Error::EmptyRange));
+        assert_eq!(try_randn::<isize>(-1), Err(Error::InvalidBound));
+        assert_eq!(try_rand_range::<u8>(9, 3), Err(Error::EmptyRange));
+        assert_eq!(
+            try_rand_range::<f64>(f64::NEG_INFINITY, 0.0),
+            Err(Error::NonFiniteFloat)
+        );
+        let n = try_rand_range::<i8>(-3, 3).unwrap();
+        assert!((-3..3).contains(&n), "{}", n);
+
+        let mut rng: Rng = Rng::with_seed(1);
+        assert_eq!(rng.try_randn(0), Err(Error::EmptyRange));
+        assert!(rng.try_rand_range(1, 2).is_ok());
+
+        let random = Random::new(1);
+        assert_eq!(random.try_randn::<u16>(0), Err(Error::EmptyRange));
+        assert_eq!(random.try_rand_range::<u16>(4, 6).map(|n| n >= 4), Ok(true));
+
+        assert_eq!(try_gen_range::<u8, _>(5..5), Err(Error::EmptyRange));
+        assert_eq!(try_gen_range(7u64..=7), Ok(7));
+        assert_eq!(try_gen_bool(2.0), Err(Error::InvalidParameter));
+        assert_eq!(try_gen_bool(1.0), Ok(true));
+        assert_eq!(random.try_gen_range(..=0u32), Ok(0));
+        assert_eq!(random.try_gen_bool(f64::NAN), Err(Error::NonFiniteFloat));
+    }
+
+    #[test]
+    fn random_try_poisoned() {
+        let random = Random::new(1);
+        // Panicking while the generator is locked poisons it
+        let result = std::panic::catch_unwind(|| random.rand_range::<u32>(5, 5));
+        assert!(result.is_err());
+
+        assert_eq!(random.try_randn::<u32>(10), Err(Error::Poisoned));
+        assert_eq!(random.try_rand_range::<u32>(1, 10), Err(Error::Poisoned));
+        assert_eq!(random.try_gen_range::<u32, _>(1..10), Err(Error::Poisoned));
+        assert_eq!(random.try_gen_bool(0.5), Err(Error::Poisoned));
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    impl Randomable for Point {
+        fn rand<E: Engine>(rng: &mut Rng<E>) -> Point {
+            Point {
+                x: i32::rand_range(rng, -100, 100),
+                y: i32::rand_range(rng, -100, 100),
+            }
+        }
+    }
+
+    fn random_pair<T: Randomable, E: Engine>(random: &Random<E>) -> (T, T) {
+        (random.rand(), random.rand())
+    }
+
+    #[test]
+    fn user_randomable() {
+        let point: Point = rand();
+        assert!((-100..100).contains(&point.x) && (-100..100).contains(&point.y));
+
+        let (a, b): (Point, Point) = random_pair(&Random::new(8));
+        assert_ne!(a, b);
+        let (c, _): (Point, Point) = random_pair(&Random::new(8));
+        assert_eq!(a, c);
+
+        let mut rng = Rng::<Pcg32>::with_seed(8);
+        let d = Point::rand(&mut rng);
+        assert!((-100..100).contains(&d.x) && (-100..100).contains(&d.y));
+        let e: Point = Random::<Xoshiro256StarStar>::with_seed(8).rand();
+        assert!((-100..100).contains(&e.x) && (-100..100).contains(&e.y));
+    }
+
+    #[test]
+    fn random_bool_and_char() {
+        let heads = (0..1000).filter(|_| rand::<bool>()).count();
+        assert!((400..600).contains(&heads), "{}", heads);
+        let hits = (0..1000).filter(|_| gen_bool(0.25)).count();
+        assert!((180..320).contains(&hits), "{}", hits);
+
+        let random = Random::new(18);
+        let hits = (0..1000).filter(|_| random.gen_bool(0.75)).count();
+        assert!((680..820).contains(&hits), "{}", hits);
+
+        for _ in 0..100 {
+            let c = gen_range('a'..='z');
+            assert!(c.is_ascii_lowercase(), "{}", c);
+            let c: char = random.rand_range('0', ':');
+            assert!(c.is_ascii_digit(), "{}", c);
+            assert!(char::from_u32(rand::<char>() as u32).is_some());
+        }
+    }
+
+    #[test]
+    fn random_composite() {
+        let (a, b, c): (u8, bool, char) = rand();
+        assert!(char::from_u32(c as u32).is_some(), "{} {}", a, b);
+
+        let values: [i64; 8] = rand();
+        assert!(values.iter().any(|v| *v != values[0]));
+
+        let values: Vec<Option<u32>> = rand_vec(1000);
+        assert_eq!(values.len(), 1000);
+        let none = values.iter().filter(|v| v.is_none()).count();
+        assert!((400..600).contains(&none), "{}", none);
+        assert!((0..100).all(|_| rand_option::<u8>(1.0).is_none()));
+
+        let random = Random::new(19);
+        let values: Vec<Result<u8, char>> = random.rand_vec(10);
+        assert_eq!(values, Random::new(19).rand_vec::<Result<u8, char>>(10));
+        let some = (0..1000)
+            .filter(|_| random.rand_option::<(u8, u8)>(0.1).is_some())
+            .count();
+        assert!((850..950).contains(&some), "{}", some);
+    }
+
+    #[test]
+    fn random_fill() {
+        let mut buf = [0u8; 1024];
+        fill_bytes(&mut buf);
+        assert!(buf.iter().filter(|b| **b == 0).count() < 32);
+
+        let mut words = vec![0u32; 99];
+        fill(&mut words[..]);
+        assert!(words.iter().all(|w| *w != 0));
+        assert_eq!(try_fill(&mut [0i64; 3]).map(|_| ()), Ok(()));
+
+        let random = Random::new(20);
+        let mut a = [0u16; 7];
+        random.fill(&mut a);
+        let mut b = [0u16; 7];
+        Random::new(20).fill(&mut b);
+        assert_eq!(a, b);
+
+        let mut bytes = [0u8; 13];
+        random.fill_bytes(&mut bytes);
+        assert_eq!(random.try_fill(&mut bytes), Ok(()));
+    }
+
+    #[test]
+    fn random_sample() {
+        let normal = distributions::Normal::new(5.0, 1.0).unwrap();
+        let mean = (0..10_000).map(|_| sample::<f64, _>(&normal)).sum::<f64>() / 10_000.0;
+        assert!((mean - 5.0).abs() < 0.1, "{}", mean);
+
+        let a: f32 = Random::new(21).sample(&normal);
+        let b: f32 = Random::new(21).sample(&normal);
+        assert_eq!(a, b);
+
+        let zipf = distributions::Zipf::new(1_000_000, 1.2).unwrap();
+        let keys: Vec<u64> = (0..1000).map(|_| sample(&zipf)).collect();
+        assert!(keys.iter().all(|k| (1..=1_000_000).contains(k)));
+        assert!(keys.iter().filter(|k| **k == 1).count() > 100);
+    }
+
+    #[test]
+    fn random_i8_exhaustive_ranges() {
+        let random = Random::new(3);
+        for min in i8::MIN..i8::MAX {
+            for max in min + 1..=i8::MAX {
+                for _ in 0..4 {
+                    let global = rand_range::<i8>(min, max);
+                    assert!(
+                        global >= min && global < max,
+                        "{} not in [{}, {})",
+                        global,
+                        min,
+                        max
+                    );
+
+                    let local: i8 = random.rand_range(min, max);
+                    assert!(
+                        local >= min && local < max,
+                        "{} not in [{}, {})",
+                        local,
+                        min,
+                        max
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn random_i16_full_range() {
+        let mut seen = vec![false; 1 << 16];
+        for _ in 0..2_000_000 {
+            seen[(rand::<i16>() as u16) as usize] = true;
+        }
+        assert!(seen.iter().all(|s| *s), "i16::rand() missed a value");
+
+        for min in (i16::MIN..i16::MAX).step_by(251) {
+            for max in (min + 1..=i16::MAX).step_by(509) {
+                let n = rand_range::<i16>(min, max);
+                assert!(n >= min && n < max, "{} not in [{}, {})", n, min, max);
+            }
+        }
+    }
+
+    #[test]
+    fn random_signed_negative() {
+        let negative = (0..1000).filter(|_| rand::<i32>() < 0).count();
+        assert!((400..600).contains(&negative), "{}", negative);
+
+        for _ in 0..100 {
+            let n = rand_range::<i64>(i64::MIN, i64::MAX);
+            assert!(n < i64::MAX);
+            let n = randn::<isize>(7);
+            assert!((0..7).contains(&n));
+        }
+    }
+
+    #[test]
+    fn thread_local_generators_are_independent() {
+        let handles: Vec<_> = (0..8).map(|_| std::thread::spawn(rand::<u64>)).collect();
+        let values: Vec<u64> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+
+        for (i, a) in values.iter().enumerate() {
+            for b in values[i + 1..].iter() {
+                assert_ne!(a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn thread_local_seed() {
+        set_seed::<u32>(42);
+        let first: Vec<u32> = (0..5).map(|_| rand()).collect();
+        set_seed::<u32>(42);
+        let second: Vec<u32> = (0..5).map(|_| rand()).collect();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn shared_mode_is_one_sequence() {
+        with_mode(GlobalMode::Shared, |rng| rng.set_seed(99));
+        let expected: Vec<u128> = (0..5)
+            .map(|_| with_mode(GlobalMode::Shared, |rng| rng.rand()))
+            .collect();
+
+        with_mode(GlobalMode::Shared, |rng| rng.set_seed(99));
+        let from_threads: Vec<u128> = (0..5)
+            .map(|_| {
+                std::thread::spawn(|| with_mode(GlobalMode::Shared, |rng| rng.rand()))
+                    .join()
+                    .unwrap()
+            })
+            .collect();
+        assert_eq!(expected, from_threads);
+
+        // No draw may be lost or repeated when threads race for the generator
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                std::thread::spawn(|| {
+                    (0..1000)
+                        .map(|_| with_mode(GlobalMode::Shared, |rng| rng.rand()))
+                        .collect::<Vec<u128>>()
+                })
+            })
+            .collect();
+        let mut values: Vec<u128> = handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap())
+            .collect();
+        values.sort_unstable();
+        values.dedup();
+        assert_eq!(values.len(), 8000);
+    }
+
     #[test]
     fn random_instance() {
         let random = Random::new(1);
         let result: u16 = random.rand_range(6, 123);
-        assert_eq!(result, 93);
+        assert_eq!(result, 97);
+    }
+
+    #[cfg(feature = "derive")]
+    mod derive {
+        use crate::*;
+
+        #[derive(Debug, PartialEq, Randomable)]
+        struct User {
+            #[rand(range = "18..=99")]
+            age: u8,
+            id: u64,
+            #[rand(range = "-1.0..1.0")]
+            score: f64,
+            role: Role,
+        }
+
+        #[derive(Debug, PartialEq, Randomable)]
+        struct Pair(#[rand(range = "..10")] u32, i8);
+
+        #[derive(Debug, PartialEq, Randomable)]
+        struct Marker;
+
+        #[derive(Debug, PartialEq, Randomable)]
+        struct Wrapper<T> {
+            inner: T,
+        }
+
+        // `U` is only a marker and `T` only drawn from a range, neither needs `Randomable`
+        #[derive(Debug, PartialEq, Randomable)]
+        struct Tagged<T, U> {
+            #[rand(range = "T::ZERO..")]
+            level: T,
+            id: u32,
+            tag: std::marker::PhantomData<U>,
+        }
+
+        #[derive(Debug, PartialEq, Randomable)]
+        enum Role {
+            Admin,
+            Member { since: u16 },
+            Guest(#[rand(range = "1..5")] u8),
+        }
+
+        #[derive(Debug, PartialEq, Randomable)]
+        enum Weighted {
+            #[weight = 9]
+            Common,
+            Rare,
+            #[weight = 0]
+            #[allow(dead_code)]
+            Never,
+        }
+
+        #[test]
+        fn derive_struct() {
+            let random = Random::new(17);
+            for _ in 0..1000 {
+                let user: User = random.rand();
+                assert!((18..=99).contains(&user.age), "{:?}", user);
+                assert!((-1.0..1.0).contains(&user.score), "{:?}", user);
+
+                let pair: Pair = rand();
+                assert!(pair.0 < 10, "{:?}", pair);
+            }
+            assert_eq!(rand::<Marker>(), Marker);
+
+            let a: User = Random::new(3).rand();
+            let b: User = Random::new(3).rand();
+            assert_eq!(a, b);
+
+            let wrapped: Wrapper<Pair> = rand();
+            assert!(wrapped.inner.0 < 10);
+
+            let tagged: Tagged<i8, String> = rand();
+            assert!(tagged.level >= 0, "{:?}", tagged);
+        }
+
+        #[test]
+        fn derive_enum_uniform() {
+            let mut rng: Rng = Rng::with_seed(17);
+            let mut counts = [0u32; 3];
+            for _ in 0..30_000 {
+                match Role::rand(&mut rng) {
+                    Role::Admin => counts[0] += 1,
+                    Role::Member { .. } => counts[1] += 1,
+                    Role::Guest(n) => {
+                        assert!((1..5).contains(&n));
+                        counts[2] += 1
+                    }
+                }
+            }
+            for count in counts.iter() {
+                assert!((9_500..10_500).contains(count), "{:?}", counts);
+            }
+        }
+
+        #[test]
+        fn derive_enum_weighted() {
+            let mut rng: Rng = Rng::with_seed(17);
+            let mut common = 0;
+            for _ in 0..10_000 {
+                match Weighted::rand(&mut rng) {
+                    Weighted::Common => common += 1,
+                    Weighted::Rare => {}
+                    Weighted::Never => panic!("a zero weight variant was drawn"),
+                }
+            }
+            assert!((8_800..9_200).contains(&common), "{}", common);
+        }
     }
 }
@@ -0,0 +1,159 @@
+use crate::engine::Engine;
+use crate::seed::expand_seed;
+
+// https://www.pcg-random.org/download.html
+const MULTIPLIER_64: u64 = 6364136223846793005;
+// The "cheap multiplier" used by the DXSM variant for both the LCG step and the output
+const CHEAP_MULTIPLIER_128: u64 = 0xda942042e4dd58b5;
+
+/// PCG with 64 bits of state and the XSH-RR output permutation.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Pcg32 {
+    state: u64,
+    increment: u64,
+}
+
+impl Pcg32 {
+    pub fn new(state: u64, stream: u64) -> Pcg32 {
+        let mut pcg = Pcg32 {
+            state: 0,
+            increment: (stream << 1) | 1,
+        };
+        pcg.step();
+        pcg.state = pcg.state.wrapping_add(state);
+        pcg.step();
+        pcg
+    }
+
+    fn step(&mut self) {
+        self.state = self
+            .state
+            .wrapping_mul(MULTIPLIER_64)
+            .wrapping_add(self.increment);
+    }
+}
+
+impl Engine for Pcg32 {
+    fn from_seed(seed: u128) -> Pcg32 {
+        let mut words = [0u64; 2];
+        expand_seed(seed, &mut words);
+        Pcg32::new(words[0], words[1])
+    }
+
+    fn next_u32(&mut self) -> u32 {
+        let old = self.state;
+        self.step();
+
+        let xorshifted = (((old >> 18) ^ old) >> 27) as u32;
+        let rot = (old >> 59) as u32;
+        xorshifted.rotate_right(rot)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let low = self.next_u32() as u64;
+        let high = self.next_u32() as u64;
+        (high << 32) | low
+    }
+}
+
+/// PCG with 128 bits of state and the DXSM output permutation.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Pcg64 {
+    state: u128,
+    increment: u128,
+}
+
+impl Pcg64 {
+    pub fn new(state: u128, stream: u128) -> Pcg64 {
+        let mut pcg = Pcg64 {
+            state: 0,
+            increment: (stream << 1) | 1,
+        };
+        pcg.step();
+        pcg.state = pcg.state.wrapping_add(state);
+        pcg.step();
+        pcg
+    }
+
+    fn step(&mut self) {
+        self.state = self
+            .state
+            .wrapping_mul(CHEAP_MULTIPLIER_128 as u128)
+            .wrapping_add(self.increment);
+    }
+}
+
+impl Engine for Pcg64 {
+    fn from_seed(seed: u128) -> Pcg64 {
+        let mut words = [0u64; 4];
+        expand_seed(seed, &mut words);
+        Pcg64::new(
+            words[0] as u128 | (words[1] as u128) << 64,
+            words[2] as u128 | (words[3] as u128) << 64,
+        )
+    }
+
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        // DXSM permutes the state from before the step
+        let mut high = (self.state >> 64) as u64;
+        let low = self.state as u64 | 1;
+        self.step();
+
+        high ^= high >> 32;
+        high = high.wrapping_mul(CHEAP_MULTIPLIER_128);
+        high ^= high >> 48;
+        high.wrapping_mul(low)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base::{RandomableRange, Rng};
+
+    #[test]
+    fn pcg32_reference() {
+        // pcg32-demo from the official C implementation
+        let mut pcg = Pcg32::new(42, 54);
+        let expected: [u32; 6] = [
+            0xa15c02b7, 0x7b47f409, 0xba1d3330, 0x83d2f293, 0xbfa4784b, 0xcbed606e,
+        ];
+        for value in expected.iter() {
+            assert_eq!(pcg.next_u32(), *value);
+        }
+    }
+
+    #[test]
+    fn pcg64_reference() {
+        // pcg_engines::cm_setseq_dxsm_128_64 from pcg-cpp
+        let mut pcg = Pcg64::new(42, 54);
+        let expected: [u64; 6] = [
+            17331114245835578256,
+            10267467544499227306,
+            9726600296081716989,
+            10165951391103677450,
+            12131334649314727261,
+            10134094537930450875,
+        ];
+        for value in expected.iter() {
+            assert_eq!(pcg.next_u64(), *value);
+        }
+    }
+
+    #[test]
+    fn pcg_as_rng_engine() {
+        let mut pcg32: Rng<Pcg32> = Rng::with_seed(7);
+        let mut pcg64: Rng<Pcg64> = Rng::with_seed(7);
+        for _ in 0..100 {
+            let value = u32::rand_range(&mut pcg32, 10, 20);
+            assert!((10..20).contains(&value), "{} not in [10, 20)", value);
+
+            let value = u32::rand_range(&mut pcg64, 10, 20);
+            assert!((10..20).contains(&value), "{} not in [10, 20)", value);
+        }
+    }
+}
@@ -0,0 +1,305 @@
+use crate::engine::Engine;
+use crate::seed::expand_seed;
+
+// Jump polynomials from the reference implementations at https://prng.di.unimi.it/
+const JUMP_256: [u64; 4] = [
+    0x180ec6d33cfd0aba,
+    0xd5a61266f0c9392c,
+    0xa9582618e03fc9aa,
+    0x39abdc4529b1661c,
+];
+const LONG_JUMP_256: [u64; 4] = [
+    0x76e15d3efefdcbbf,
+    0xc5004e441c522fb3,
+    0x77710069854ee241,
+    0x39109bb02acbe635,
+];
+const JUMP_128: [u64; 2] = [0x2bd7a6a6e99c2ddc, 0x0992ccaf6a6fca05];
+const LONG_JUMP_128: [u64; 2] = [0x360fd5f2cf8d5d99, 0x9c6e6877736c46e3];
+
+// Used in place of an all-zero state, which the xor-shift family can never leave
+const NONZERO_STATE: u64 = 0x9e3779b97f4a7c15;
+
+fn state_256(seed: u128) -> [u64; 4] {
+    let mut s = [0u64; 4];
+    expand_seed(seed, &mut s);
+    if s == [0; 4] {
+        s[0] = NONZERO_STATE;
+    }
+    s
+}
+
+fn state_128(seed: u128) -> [u64; 2] {
+    let mut s = [0u64; 2];
+    expand_seed(seed, &mut s);
+    if s == [0; 2] {
+        s[0] = NONZERO_STATE;
+    }
+    s
+}
+
+fn step_256(s: &mut [u64; 4]) {
+    let t = s[1] << 17;
+
+    s[2] ^= s[0];
+    s[3] ^= s[1];
+    s[1] ^= s[2];
+    s[0] ^= s[3];
+
+    s[2] ^= t;
+    s[3] = s[3].rotate_left(45);
+}
+
+fn jump_256(s: &mut [u64; 4], polynomial: &[u64; 4]) {
+    let mut jumped = [0u64; 4];
+    for word in polynomial.iter() {
+        for bit in 0..64 {
+            if word & (1 << bit) != 0 {
+                for (j, v) in jumped.iter_mut().zip(s.iter()) {
+                    *j ^= v;
+                }
+            }
+            step_256(s);
+        }
+    }
+    *s = jumped;
+}
+
+/// xoshiro256**, the all-purpose 64-bit generator of the xoshiro family.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Xoshiro256StarStar {
+    s: [u64; 4],
+}
+
+impl Xoshiro256StarStar {
+    pub fn from_state(s: [u64; 4]) -> Xoshiro256StarStar {
+        Xoshiro256StarStar { s }
+    }
+
+    /// Equivalent to 2^128 calls to next_u64, gives 2^128 non-overlapping streams
+    pub fn jump(&mut self) {
+        jump_256(&mut self.s, &JUMP_256);
+    }
+
+    /// Equivalent to 2^192 calls to next_u64, gives 2^64 starting points for jump()
+    pub fn long_jump(&mut self) {
+        jump_256(&mut self.s, &LONG_JUMP_256);
+    }
+}
+
+impl Engine for Xoshiro256StarStar {
+    fn from_seed(seed: u128) -> Xoshiro256StarStar {
+        Xoshiro256StarStar::from_state(state_256(seed))
+    }
+
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let result = self.s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        step_256(&mut self.s);
+        result
+    }
+}
+
+/// xoshiro256+, slightly faster than xoshiro256** with weaker low bits.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Xoshiro256Plus {
+    s: [u64; 4],
+}
+
+impl Xoshiro256Plus {
+    pub fn from_state(s: [u64; 4]) -> Xoshiro256Plus {
+        Xoshiro256Plus { s }
+    }
+
+    pub fn jump(&mut self) {
+        jump_256(&mut self.s, &JUMP_256);
+    }
+
+    pub fn long_jump(&mut self) {
+        jump_256(&mut self.s, &LONG_JUMP_256);
+    }
+}
+
+impl Engine for Xoshiro256Plus {
+    fn from_seed(seed: u128) -> Xoshiro256Plus {
+        Xoshiro256Plus::from_state(state_256(seed))
+    }
+
+    fn next_u32(&mut self) -> u32 {
+        // The lowest bits are the weakest ones, keep the upper half
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let result = self.s[0].wrapping_add(self.s[3]);
+        step_256(&mut self.s);
+        result
+    }
+}
+
+/// xoroshiro128++, the small-state member of the family.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Xoroshiro128PlusPlus {
+    s: [u64; 2],
+}
+
+impl Xoroshiro128PlusPlus {
+    pub fn from_state(s: [u64; 2]) -> Xoroshiro128PlusPlus {
+        Xoroshiro128PlusPlus { s }
+    }
+
+    /// Equivalent to 2^64 calls to next_u64
+    pub fn jump(&mut self) {
+        self.jump_with(&JUMP_128);
+    }
+
+    /// Equivalent to 2^96 calls to next_u64
+    pub fn long_jump(&mut self) {
+        self.jump_with(&LONG_JUMP_128);
+    }
+
+    fn jump_with(&mut self, polynomial: &[u64; 2]) {
+        let mut jumped = [0u64; 2];
+        for word in polynomial.iter() {
+            for bit in 0..64 {
+                if word & (1 << bit) != 0 {
+                    jumped[0] ^= self.s[0];
+                    jumped[1] ^= self.s[1];
+                }
+                self.next_u64();
+            }
+        }
+        self.s = jumped;
+    }
+}
+
+impl Engine for Xoroshiro128PlusPlus {
+    fn from_seed(seed: u128) -> Xoroshiro128PlusPlus {
+        Xoroshiro128PlusPlus::from_state(state_128(seed))
+    }
+
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let [s0, mut s1] = self.s;
+        let result = s0.wrapping_add(s1).rotate_left(17).wrapping_add(s0);
+
+        s1 ^= s0;
+        self.s = [s0.rotate_left(49) ^ s1 ^ (s1 << 21), s1.rotate_left(28)];
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn xoshiro256starstar_reference() {
+        // https://prng.di.unimi.it/xoshiro256starstar.c
+        let mut rng = Xoshiro256StarStar::from_state([1, 2, 3, 4]);
+        let expected: [u64; 10] = [
+            11520,
+            0,
+            1509978240,
+            1215971899390074240,
+            1216172134540287360,
+            607988272756665600,
+            16172922978634559625,
+            8476171486693032832,
+            10595114339597558777,
+            2904607092377533576,
+        ];
+        for value in expected.iter() {
+            assert_eq!(rng.next_u64(), *value);
+        }
+    }
+
+    #[test]
+    fn xoshiro256plus_reference() {
+        // https://prng.di.unimi.it/xoshiro256plus.c
+        let mut rng = Xoshiro256Plus::from_state([1, 2, 3, 4]);
+        let expected: [u64; 10] = [
+            5,
+            211106232532999,
+            211106635186183,
+            9223759065350669058,
+            9250833439874351877,
+            13862484359527728515,
+            2346507365006083650,
+            1168864526675804870,
+            34095955243042024,
+            3466914240207415127,
+        ];
+        for value in expected.iter() {
+            assert_eq!(rng.next_u64(), *value);
+        }
+    }
+
+    #[test]
+    fn xoroshiro128plusplus_reference() {
+        // https://prng.di.unimi.it/xoroshiro128plusplus.c
+        let mut rng = Xoroshiro128PlusPlus::from_state([1, 2]);
+        let expected: [u64; 10] = [
+            393217,
+            669327710093319,
+            1732421326133921491,
+            11394790081659126983,
+            9555452776773192676,
+            3586421180005889563,
+            1691397964866707553,
+            10735626796753111697,
+            15216282715349408991,
+            14247243556711267923,
+        ];
+        for value in expected.iter() {
+            assert_eq!(rng.next_u64(), *value);
+        }
+    }
+
+    #[test]
+    fn jump_polynomial_advances_state() {
+        // The polynomial x^3 must land exactly three steps ahead
+        let mut jumped = [1, 2, 3, 4];
+        jump_256(&mut jumped, &[1 << 3, 0, 0, 0]);
+
+        let mut stepped = [1, 2, 3, 4];
+        for _ in 0..3 {
+            step_256(&mut stepped);
+        }
+        assert_eq!(jumped, stepped);
+    }
+
+    #[test]
+    fn jumps_split_streams() {
+        let base = Xoshiro256StarStar::from_seed(42);
+
+        let mut jumped = base;
+        jumped.jump();
+        let mut long_jumped = base;
+        long_jumped.long_jump();
+
+        assert_ne!(base, jumped);
+        assert_ne!(base, long_jumped);
+        assert_ne!(jumped, long_jumped);
+
+        let mut small = Xoroshiro128PlusPlus::from_seed(42);
+        let before = small;
+        small.jump();
+        assert_ne!(before, small);
+        small.long_jump();
+        assert_ne!(before, small);
+    }
+
+    #[test]
+    fn zero_seed_is_usable() {
+        assert_ne!(Xoshiro256StarStar::from_seed(0).s, [0; 4]);
+        assert_ne!(Xoshiro256Plus::from_seed(0).s, [0; 4]);
+        assert_ne!(Xoroshiro128PlusPlus::from_seed(0).s, [0; 2]);
+    }
+}
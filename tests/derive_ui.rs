@@ -0,0 +1,8 @@
+#![cfg(feature = "derive")]
+
+// The derive's error messages, the expected output lives next to each case in tests/ui
+#[test]
+fn derive_errors() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
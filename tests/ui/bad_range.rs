@@ -0,0 +1,9 @@
+use simplerand::Randomable;
+
+#[derive(Randomable)]
+struct User {
+    #[rand(range = "18..=")]
+    age: u8,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use simplerand::Randomable;
+
+#[derive(Randomable)]
+enum Role {
+    #[weight = 0.5]
+    Admin,
+    Guest,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use simplerand::Randomable;
+
+#[derive(Randomable)]
+union Bits {
+    int: u32,
+    float: f32,
+}
+
+fn main() {}